@@ -1,6 +1,6 @@
 use std::alloc::{GlobalAlloc, System};
 
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use rc_vec::{ArcVec, RcVec};
 
 //#[global_allocator]
@@ -42,5 +42,78 @@ fn basic(c: &mut Criterion) {
     }));
 }
 
-criterion_group!(benches, basic);
+fn growth(c: &mut Criterion) {
+    let n: usize = 20000;
+    c.bench_function("Vec reserve_exact", |b| b.iter(|| {
+        let mut vec = Vec::new();
+
+        for i in 0..n {
+            vec.reserve_exact(1);
+            vec.push(i);
+        }
+    }));
+    c.bench_function("RcVec reserve_exact realloc", |b| b.iter(|| {
+        let mut vec = RcVec::new();
+
+        for i in 0..n {
+            vec.reserve_exact(1);
+            vec.push(i);
+        }
+    }));
+    c.bench_function("RcVec reserve_exact copy", |b| b.iter(|| {
+        let mut vec = RcVec::new();
+
+        for i in 0..n {
+            // always allocates a new block and copy, like not realloc
+            let mut new = RcVec::with_capacity(vec.len() + 1);
+            new.append(&mut vec);
+            vec = new;
+            vec.push(i);
+        }
+    }));
+    c.bench_function("RcVec into_rc_slice shrink", |b| b.iter(|| {
+        let mut vec = RcVec::with_capacity(n * 2);
+        vec.extend(0..n);
+        vec.into_rc_slice()
+    }));
+}
+
+fn reserve(c: &mut Criterion) {
+    let n: usize = 1 << 24;
+    c.bench_function("Vec reserve large", |b| b.iter(|| {
+        let mut vec = vec![0u64];
+        vec.reserve(black_box(n));
+        vec
+    }));
+    c.bench_function("RcVec reserve large", |b| b.iter(|| {
+        let mut vec = rc_vec::rc_vec![0u64];
+        vec.reserve(black_box(n));
+        vec
+    }));
+    c.bench_function("ArcVec reserve large", |b| b.iter(|| {
+        let mut vec = rc_vec::arc_vec![0u64];
+        vec.reserve(black_box(n));
+        vec
+    }));
+
+    let n: usize = 1 << 20;
+    c.bench_function("Vec with_capacity push", |b| b.iter(|| {
+        let mut vec = Vec::with_capacity(n);
+
+        for i in 0..n {
+            vec.push(black_box(i));
+        }
+        vec
+    }));
+    c.bench_function("RcVec with_capacity push", |b| b.iter(|| {
+        let mut vec = RcVec::with_capacity(n);
+
+        for i in 0..n {
+            vec.push(black_box(i));
+        }
+        vec
+    }));
+}
+
+criterion_group!(benches, basic, growth, reserve);
 criterion_main!(benches);
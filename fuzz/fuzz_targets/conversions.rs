@@ -0,0 +1,109 @@
+//! Convert `RcVec<Tracked>` between Rc, UniqRc, Box and arrays
+
+#![no_main]
+
+use std::rc::Rc;
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rc_vec::{unique_rc::UniqRc, RcVec};
+use rc_vec_fuzz::{check_leaks, Tracked};
+
+#[derive(Debug, Arbitrary)]
+enum Conversion {
+    FromRc { shared: bool },
+    FromRcOrClone { shared: bool },
+    FromUniqRc,
+    IntoRc,
+    IntoUniqRc,
+    IntoBox,
+    IntoArray,
+    IntoRcArray,
+    IntoIter { take: usize, back: usize },
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    values: Vec<u8>,
+    extra_capacity: u8,
+    conversions: Vec<Conversion>,
+}
+
+fuzz_target!(|input: Input| {
+    check_leaks(|| {
+        let mut vec: RcVec<Tracked> = RcVec::with_capacity(input.extra_capacity.into());
+        vec.extend(input.values.iter().copied().map(Tracked::new));
+
+        for conversion in input.conversions {
+            let expected: Vec<u8> = vec.iter().map(|t| t.0).collect();
+
+            vec = match conversion {
+                Conversion::FromRc { shared } => {
+                    let rc: Rc<[Tracked]> = vec.into();
+                    let other = shared.then(|| rc.clone());
+                    match RcVec::try_from_rc(rc) {
+                        Ok(vec) => {
+                            assert!(other.is_none());
+                            vec
+                        },
+                        Err(rc) => {
+                            assert!(other.is_some());
+                            drop(other);
+                            RcVec::try_from_rc(rc).unwrap()
+                        },
+                    }
+                },
+                Conversion::FromRcOrClone { shared } => {
+                    let rc = vec.into_rc_slice();
+                    let other = shared.then(|| rc.clone());
+                    let (vec, reused) = RcVec::from_rc_or_clone(rc);
+                    assert_eq!(reused, other.is_none());
+                    vec
+                },
+                Conversion::FromUniqRc => {
+                    let uniq: UniqRc<[Tracked]> = vec.into_uniq_slice();
+                    RcVec::from(uniq)
+                },
+                Conversion::IntoRc => RcVec::from(vec.into_rc_slice()),
+                Conversion::IntoUniqRc => RcVec::from(vec.into_uniq_slice()),
+                Conversion::IntoBox => {
+                    let boxed: Box<[Tracked]> = vec.into();
+                    boxed.iter().cloned().collect()
+                },
+                Conversion::IntoArray => match <[Tracked; 4]>::try_from(vec) {
+                    Ok(array) => {
+                        assert_eq!(expected.len(), 4);
+                        RcVec::from(array)
+                    },
+                    Err(vec) => {
+                        assert_ne!(expected.len(), 4);
+                        vec
+                    },
+                },
+                Conversion::IntoRcArray => match Rc::<[Tracked; 3]>::try_from(vec) {
+                    Ok(rc) => {
+                        assert_eq!(expected.len(), 3);
+                        RcVec::from(rc as Rc<[Tracked]>)
+                    },
+                    Err(vec) => {
+                        assert_ne!(expected.len(), 3);
+                        vec
+                    },
+                },
+                Conversion::IntoIter { take, back } => {
+                    let mut iter = vec.into_iter();
+                    let mut values: Vec<u8> = iter.by_ref().take(take).map(|t| t.0).collect();
+                    let tail: Vec<u8> = iter.by_ref().rev().take(back).map(|t| t.0).collect();
+                    values.extend(iter.map(|t| t.0));
+                    values.extend(tail.into_iter().rev());
+                    assert_eq!(values, expected);
+
+                    expected.iter().copied().map(Tracked::new).collect()
+                },
+            };
+
+            assert!(vec.iter().map(|t| t.0).eq(expected));
+            assert_eq!(rc_vec_fuzz::alive(), vec.len() as isize);
+        }
+    });
+});
@@ -0,0 +1,175 @@
+//! Apply operation sequences to `RcVec<Tracked>`, and compare with `Vec<u8>`
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rc_vec::RcVec;
+use rc_vec_fuzz::{check_leaks, Tracked};
+
+#[derive(Debug, Arbitrary)]
+enum Op {
+    Push(u8),
+    Pop,
+    Insert(usize, u8),
+    InsertMany(usize, Vec<u8>),
+    Remove(usize),
+    SwapRemove(usize),
+    Drain(usize, usize, usize),
+    Splice(usize, usize, Vec<u8>),
+    ExtractIf(usize, usize, u8),
+    Retain(u8),
+    DedupByKey(u8),
+    SplitOff(usize),
+    Append(Vec<u8>),
+    ExtendFromWithin(usize, usize),
+    Truncate(usize),
+    Resize(u8, u8),
+    Reserve(u8),
+    ShrinkTo(u8),
+    ShrinkToFit,
+    RoundTripRc,
+    Clone,
+    Clear,
+}
+
+const MAX_LEN: usize = 1024;
+
+fn range(len: usize, a: usize, b: usize) -> std::ops::Range<usize> {
+    let start = a % (len + 1);
+    start..start + b % (len - start + 1)
+}
+
+fn tracked(values: &[u8]) -> impl Iterator<Item = Tracked> + '_ {
+    values.iter().copied().map(Tracked::new)
+}
+
+fuzz_target!(|ops: Vec<Op>| {
+    check_leaks(|| {
+        let mut vec: RcVec<Tracked> = RcVec::new();
+        let mut model: Vec<u8> = Vec::new();
+
+        for op in ops {
+            let len = model.len();
+
+            match op {
+                Op::Push(v) => {
+                    vec.push(Tracked::new(v));
+                    model.push(v);
+                },
+                Op::Pop => {
+                    assert_eq!(vec.pop().map(|t| t.0), model.pop());
+                },
+                Op::Insert(i, v) => {
+                    vec.insert(i % (len + 1), Tracked::new(v));
+                    model.insert(i % (len + 1), v);
+                },
+                Op::InsertMany(i, values) => {
+                    vec.insert_many(i % (len + 1), tracked(&values));
+                    model.splice(i % (len + 1)..i % (len + 1), values);
+                },
+                Op::Remove(i) if len != 0 => {
+                    assert_eq!(vec.remove(i % len).0, model.remove(i % len));
+                },
+                Op::SwapRemove(i) if len != 0 => {
+                    assert_eq!(vec.swap_remove(i % len).0, model.swap_remove(i % len));
+                },
+                Op::Remove(_) | Op::SwapRemove(_) => (),
+                Op::Drain(a, b, take) => {
+                    let range = range(len, a, b);
+                    let drained: Vec<u8> = vec.drain(range.clone())
+                        .take(take)
+                        .map(|t| t.0)
+                        .collect();
+                    let expected: Vec<u8> = model.drain(range).take(take).collect();
+                    assert_eq!(drained, expected);
+                },
+                Op::Splice(a, b, values) => {
+                    let range = range(len, a, b);
+                    let removed: Vec<u8> = vec.splice(range.clone(), tracked(&values))
+                        .map(|t| t.0)
+                        .collect();
+                    let expected: Vec<u8> = model.splice(range, values).collect();
+                    assert_eq!(removed, expected);
+                },
+                Op::ExtractIf(a, b, m) => {
+                    let range = range(len, a, b);
+                    let m = m.max(1);
+                    let extracted: Vec<u8> = vec.extract_if(range.clone(), |t| t.0 % m == 0)
+                        .map(|t| t.0)
+                        .collect();
+                    let expected: Vec<u8> = model.extract_if(range, |v| *v % m == 0).collect();
+                    assert_eq!(extracted, expected);
+                },
+                Op::Retain(m) => {
+                    let m = m.max(1);
+                    vec.retain(|t| t.0 % m != 0);
+                    model.retain(|v| v % m != 0);
+                },
+                Op::DedupByKey(m) => {
+                    let m = m.max(1);
+                    vec.dedup_by_key(|t| t.0 / m);
+                    model.dedup_by_key(|v| *v / m);
+                },
+                Op::SplitOff(at) => {
+                    let other = vec.split_off(at % (len + 1));
+                    let expected = model.split_off(at % (len + 1));
+                    assert!(other.iter().map(|t| t.0).eq(expected));
+                },
+                Op::Append(values) => {
+                    let mut other: RcVec<Tracked> = tracked(&values).collect();
+                    vec.append(&mut other);
+                    assert!(other.is_empty());
+                    model.extend(values);
+                },
+                Op::ExtendFromWithin(a, b) => {
+                    let range = range(len, a, b);
+                    vec.extend_from_within(range.clone());
+                    model.extend_from_within(range);
+                },
+                Op::Truncate(n) => {
+                    vec.truncate(n % (len + 1));
+                    model.truncate(n % (len + 1));
+                },
+                Op::Resize(n, v) => {
+                    vec.resize(n.into(), Tracked::new(v));
+                    model.resize(n.into(), v);
+                },
+                Op::Reserve(n) => {
+                    vec.reserve(n.into());
+                    assert!(vec.capacity() >= len + usize::from(n));
+                },
+                Op::ShrinkTo(n) => {
+                    vec.shrink_to(n.into());
+                    assert!(vec.capacity() >= len);
+                },
+                Op::ShrinkToFit => {
+                    vec.shrink_to_fit();
+                    assert!(vec.capacity() >= len);
+                },
+                Op::RoundTripRc => {
+                    let rc = vec.into_rc_slice();
+                    vec = RcVec::try_from_rc(rc).unwrap();
+                },
+                Op::Clone => {
+                    let cloned = vec.clone();
+                    assert_eq!(cloned, vec);
+                    vec = cloned;
+                },
+                Op::Clear => {
+                    vec.clear();
+                    model.clear();
+                },
+            }
+
+            // Keep the input fast, `ExtendFromWithin` doubles the length
+            if model.len() > MAX_LEN {
+                vec.truncate(MAX_LEN);
+                model.truncate(MAX_LEN);
+            }
+
+            assert!(vec.iter().map(|t| t.0).eq(model.iter().copied()));
+            assert_eq!(rc_vec_fuzz::alive(), vec.len() as isize);
+        }
+    });
+});
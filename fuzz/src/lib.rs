@@ -0,0 +1,45 @@
+//! Shared helpers of the fuzz targets
+
+use std::cell::Cell;
+
+thread_local! {
+    static ALIVE: Cell<isize> = const { Cell::new(0) };
+}
+
+/// Count of alive [`Tracked`] values
+pub fn alive() -> isize {
+    ALIVE.with(Cell::get)
+}
+
+/// Element tracking its drops, catch double drops and leaks
+#[derive(Debug, PartialEq, Eq)]
+pub struct Tracked(pub u8);
+
+impl Tracked {
+    pub fn new(value: u8) -> Self {
+        ALIVE.with(|alive| alive.set(alive.get() + 1));
+        Self(value)
+    }
+}
+
+impl Clone for Tracked {
+    fn clone(&self) -> Self {
+        Self::new(self.0)
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        ALIVE.with(|alive| {
+            assert!(alive.get() > 0, "double drop of {}", self.0);
+            alive.set(alive.get() - 1);
+        });
+    }
+}
+
+/// Run `f`, then check that every created [`Tracked`] has been dropped once
+pub fn check_leaks(f: impl FnOnce()) {
+    let before = alive();
+    f();
+    assert_eq!(alive(), before, "leaked or double dropped");
+}
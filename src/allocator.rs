@@ -0,0 +1,147 @@
+//! Memory allocator of [`SharedVec`](crate::SharedVec)
+//!
+//! With the `allocator_api2` feature, this is re-exported from `allocator_api2`,
+//! otherwise only the fallback [`Global`] is available
+//!
+//! The buffer is allocated from the allocator with the layout of `Rc<[T]>`,
+//! so only the buffer of [`Global`] can be converted into [`Rc`](alloc::rc::Rc)
+//! on stable
+
+#[cfg(feature = "allocator_api2")]
+pub use allocator_api2::alloc::{AllocError, Allocator, Global};
+
+#[cfg(not(feature = "allocator_api2"))]
+pub use fallback::{AllocError, Allocator, Global};
+
+#[cfg(not(feature = "allocator_api2"))]
+mod fallback {
+    use core::{alloc::Layout, fmt, ptr::{self, NonNull}};
+
+    mod sealed {
+        pub trait Sealed {}
+    }
+
+    /// The error type of [`Allocator`]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct AllocError;
+
+    impl fmt::Display for AllocError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("memory allocation failed")
+        }
+    }
+
+    /// Fallback of `allocator_api2::alloc::Allocator`, only implemented by [`Global`]
+    ///
+    /// Enable the `allocator_api2` feature to use custom allocators
+    ///
+    /// # Safety
+    /// See `allocator_api2::alloc::Allocator`
+    pub unsafe trait Allocator: sealed::Sealed {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError>;
+
+        /// # Safety
+        /// - `ptr` is currently allocated by this allocator with `layout`
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+
+        /// # Safety
+        /// - `ptr` is currently allocated by this allocator with `old_layout`
+        /// - `new_layout.size() >= old_layout.size()`
+        unsafe fn grow(
+            &self,
+            ptr: NonNull<u8>,
+            old_layout: Layout,
+            new_layout: Layout,
+        ) -> Result<NonNull<[u8]>, AllocError>;
+
+        /// # Safety
+        /// - `ptr` is currently allocated by this allocator with `old_layout`
+        /// - `new_layout.size() <= old_layout.size()`
+        unsafe fn shrink(
+            &self,
+            ptr: NonNull<u8>,
+            old_layout: Layout,
+            new_layout: Layout,
+        ) -> Result<NonNull<[u8]>, AllocError>;
+    }
+
+    /// The global memory allocator, like `alloc::alloc::Global`
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct Global;
+
+    impl sealed::Sealed for Global {}
+
+    impl Global {
+        fn realloc(
+            &self,
+            ptr: NonNull<u8>,
+            old_layout: Layout,
+            new_layout: Layout,
+        ) -> Result<NonNull<[u8]>, AllocError> {
+            if old_layout.size() == 0 || old_layout.align() != new_layout.align() {
+                let new = self.allocate(new_layout)?;
+                unsafe {
+                    let size = old_layout.size().min(new_layout.size());
+                    ptr::copy_nonoverlapping(ptr.as_ptr(), new.cast().as_ptr(), size);
+                    self.deallocate(ptr, old_layout);
+                }
+                return Ok(new);
+            }
+
+            if new_layout.size() == 0 {
+                unsafe { self.deallocate(ptr, old_layout) };
+                return self.allocate(new_layout);
+            }
+
+            let raw = unsafe {
+                alloc::alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size())
+            };
+            NonNull::new(raw)
+                .map(|raw| NonNull::slice_from_raw_parts(raw, new_layout.size()))
+                .ok_or(AllocError)
+        }
+    }
+
+    unsafe impl Allocator for Global {
+        #[inline]
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            let raw = if layout.size() == 0 {
+                ptr::without_provenance_mut(layout.align())
+            } else {
+                unsafe { alloc::alloc::alloc(layout) }
+            };
+            NonNull::new(raw)
+                .map(|raw| NonNull::slice_from_raw_parts(raw, layout.size()))
+                .ok_or(AllocError)
+        }
+
+        #[inline]
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            if layout.size() != 0 {
+                alloc::alloc::dealloc(ptr.as_ptr(), layout);
+            }
+        }
+
+        #[inline]
+        unsafe fn grow(
+            &self,
+            ptr: NonNull<u8>,
+            old_layout: Layout,
+            new_layout: Layout,
+        ) -> Result<NonNull<[u8]>, AllocError> {
+            debug_assert!(new_layout.size() >= old_layout.size());
+            self.realloc(ptr, old_layout, new_layout)
+        }
+
+        #[inline]
+        unsafe fn shrink(
+            &self,
+            ptr: NonNull<u8>,
+            old_layout: Layout,
+            new_layout: Layout,
+        ) -> Result<NonNull<[u8]>, AllocError> {
+            debug_assert!(new_layout.size() <= old_layout.size());
+            self.realloc(ptr, old_layout, new_layout)
+        }
+    }
+}
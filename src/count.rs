@@ -0,0 +1,193 @@
+//! Strong count operations on the header of the `Rc<[T]>` allocation,
+//! see the layout invariant of `raw::alloc_guard`
+//!
+//! The standard library has no `Arc::into_inner` for the unsized `[T]`,
+//! so the last of the concurrent owners is found by the count here
+
+use core::cell::Cell;
+
+/// The strong count of [`Rc`](alloc::rc::Rc) or [`Arc`](alloc::sync::Arc),
+/// the same layout as `usize`
+pub(crate) trait StrongCount {
+    /// Decrement the count like dropping an owner,
+    /// returns `true` if it is the last owner
+    ///
+    /// The count of the last owner is restored to `1` without decrement,
+    /// then the allocation is unique, because `Weak` never exists
+    fn release(&self) -> bool;
+}
+
+impl StrongCount for Cell<usize> {
+    fn release(&self) -> bool {
+        let count = self.get();
+        if count != 1 {
+            self.set(count - 1);
+        }
+        count == 1
+    }
+}
+
+macro_rules! impl_atomic {
+    ($($atomic:ident)::+) => {
+        impl StrongCount for $($atomic)::+::AtomicUsize {
+            fn release(&self) -> bool {
+                use $($atomic)::+::{fence, Ordering};
+
+                // like `Arc::drop`, synchronize with the other owners
+                if self.fetch_sub(1, Ordering::Release) != 1 {
+                    return false;
+                }
+                fence(Ordering::Acquire);
+
+                // no other owner can observe the count now
+                self.store(1, Ordering::Relaxed);
+                true
+            }
+        }
+    };
+}
+
+impl_atomic!(core::sync::atomic);
+
+// the models of the `Arc` buffer, the header count is replaced by the loom atomic
+#[cfg(loom)]
+impl_atomic!(loom::sync::atomic);
+
+/// Run with loom:
+///
+/// ```sh
+/// RUSTFLAGS="--cfg loom" cargo test --lib --release count::
+/// ```
+#[cfg(all(test, loom))]
+mod tests {
+    use alloc::boxed::Box;
+    use loom::{
+        cell::UnsafeCell,
+        sync::{atomic::{AtomicUsize, Ordering}, Arc},
+        thread,
+    };
+
+    use super::StrongCount;
+
+    /// The buffer of a frozen `ArcVec`, the element is a loom `Arc`,
+    /// so loom reports the element which is never dropped
+    struct Buf {
+        strong: AtomicUsize,
+        elem: UnsafeCell<Option<Arc<()>>>,
+    }
+
+    /// An owner of the buffer like `CowArcVec`
+    struct Owner(*const Buf);
+
+    unsafe impl Send for Owner {}
+
+    impl Owner {
+        fn freeze(elem: Arc<()>) -> Self {
+            let buf = Buf {
+                strong: AtomicUsize::new(1),
+                elem: UnsafeCell::new(Some(elem)),
+            };
+            Self(Box::into_raw(Box::new(buf)))
+        }
+
+        fn buf(&self) -> &Buf {
+            unsafe { &*self.0 }
+        }
+
+        fn share(&self) -> Self {
+            // like `Arc::clone`
+            self.buf().strong.fetch_add(1, Ordering::Relaxed);
+            Self(self.0)
+        }
+
+        fn read(&self) -> bool {
+            self.buf().elem.with(|elem| unsafe { (*elem).is_some() })
+        }
+
+        /// Thaw like `try_into_rc_vec`, the check of `Arc::get_mut` without `Weak`
+        fn try_thaw(self) -> Result<Box<Buf>, Self> {
+            if self.buf().strong.load(Ordering::Acquire) != 1 {
+                return Err(self);
+            }
+            let this = core::mem::ManuallyDrop::new(self);
+            Ok(unsafe { Box::from_raw(this.0.cast_mut()) })
+        }
+    }
+
+    impl Drop for Owner {
+        fn drop(&mut self) {
+            if self.buf().strong.release() {
+                let buf = unsafe { Box::from_raw(self.0.cast_mut()) };
+                buf.elem.with_mut(|elem| unsafe { (*elem).take() }).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn freeze_share_drop() {
+        loom::model(|| {
+            let elem = Arc::new(());
+            let owner = Owner::freeze(elem.clone());
+            let other = owner.share();
+
+            let handle = thread::spawn(move || {
+                assert!(other.read());
+                drop(other);
+            });
+
+            assert!(owner.read());
+            drop(owner);
+            handle.join().unwrap();
+
+            // dropped exactly once
+            assert_eq!(Arc::strong_count(&elem), 1);
+        });
+    }
+
+    #[test]
+    fn thaw_at_most_once() {
+        loom::model(|| {
+            let elem = Arc::new(());
+            let owner = Owner::freeze(elem.clone());
+            let other = owner.share();
+
+            let thaw = |owner: Owner| match owner.try_thaw() {
+                Ok(buf) => {
+                    buf.elem.with_mut(|elem| unsafe { (*elem).take() }).unwrap();
+                    true
+                },
+                Err(owner) => {
+                    drop(owner);
+                    false
+                },
+            };
+
+            let handle = thread::spawn(move || thaw(other));
+            let this = thaw(owner);
+            let that = handle.join().unwrap();
+
+            assert!(!(this && that));
+            assert_eq!(Arc::strong_count(&elem), 1);
+        });
+    }
+
+    #[test]
+    fn thaw_after_join() {
+        loom::model(|| {
+            let elem = Arc::new(());
+            let owner = Owner::freeze(elem.clone());
+            let other = owner.share();
+
+            let handle = thread::spawn(move || {
+                assert!(other.read());
+                drop(other);
+            });
+            handle.join().unwrap();
+
+            // the other owner has been dropped, so the buffer is unique now
+            let Ok(buf) = owner.try_thaw() else { panic!("shared") };
+            buf.elem.with_mut(|elem| unsafe { (*elem).take() }).unwrap();
+            assert_eq!(Arc::strong_count(&elem), 1);
+        });
+    }
+}
@@ -0,0 +1,95 @@
+use core::{alloc::Layout, fmt};
+
+/// The error type for `try_reserve` methods, like [`alloc::collections::TryReserveError`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TryReserveError {
+    kind: TryReserveErrorKind,
+}
+
+impl TryReserveError {
+    /// Details about the allocation that caused the error
+    pub fn kind(&self) -> TryReserveErrorKind {
+        self.kind.clone()
+    }
+}
+
+/// Details of the allocation that caused a [`TryReserveError`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TryReserveErrorKind {
+    /// Error due to the computed capacity exceeding the collection's maximum
+    /// (usually `isize::MAX` bytes).
+    CapacityOverflow,
+
+    /// The memory allocator returned an error
+    AllocError {
+        /// The layout of allocation request that failed
+        layout: Layout,
+    },
+}
+
+impl From<TryReserveErrorKind> for TryReserveError {
+    #[inline]
+    fn from(kind: TryReserveErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")?;
+        let reason = match self.kind {
+            TryReserveErrorKind::CapacityOverflow => {
+                " because the computed capacity exceeded the collection's maximum"
+            },
+            TryReserveErrorKind::AllocError { .. } => {
+                " because the memory allocator returned an error"
+            },
+        };
+        f.write_str(reason)
+    }
+}
+
+impl core::error::Error for TryReserveError {}
+
+/// The error type for [`try_push`](crate::SharedVec::try_push),
+/// keeps the value that could not be pushed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TryPushError<T> {
+    value: T,
+    error: TryReserveError,
+}
+
+impl<T> TryPushError<T> {
+    pub(crate) fn new(value: T, error: TryReserveError) -> Self {
+        Self { value, error }
+    }
+
+    /// Returns the value that was attempted to push
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Returns the value that was attempted to push
+    pub fn as_value(&self) -> &T {
+        &self.value
+    }
+
+    /// Fetch a [`TryReserveError`] to get more details about the failure
+    pub fn reserve_error(&self) -> TryReserveError {
+        self.error.clone()
+    }
+}
+
+impl<T> From<TryPushError<T>> for TryReserveError {
+    fn from(value: TryPushError<T>) -> Self {
+        value.error
+    }
+}
+
+impl<T> fmt::Display for TryPushError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl<T: fmt::Debug> core::error::Error for TryPushError<T> {}
@@ -0,0 +1,124 @@
+//! Growth policies of the buffer, selected by the last type parameter of [`SharedVec`]
+//!
+//! The policy is used by the amortized growth, e.g [`push`], [`reserve`] and [`insert`],
+//! but not by the exact growth, e.g [`reserve_exact`]
+//!
+//! The vector of any policy is created by [`Default`] and [`FromIterator`],
+//! while [`new`], [`with_capacity`] and the `_in` constructors only create the default
+//! [`Doubling`] policy, like `HashMap::new` of `RandomState`,
+//! so that `RcVec::new()` infers its type,
+//! use [`with_growth_policy`] to change the policy without reallocation
+//!
+//! [`SharedVecDeque`], [`SharedSmallVec`] and [`SharedVecCursor`]
+//! have no policy parameter, they always use [`Doubling`]
+//!
+//! # Examples
+//!
+//! ```
+//! use rc_vec::{growth::OneAndHalf, RcVec};
+//!
+//! let mut vec = RcVec::<u8>::with_capacity(16)
+//!     .with_growth_policy::<OneAndHalf>();
+//! vec.extend([0; 17]);
+//! assert_eq!(vec.capacity(), 24);
+//!
+//! let vec: RcVec<u8, _, OneAndHalf> = (0..3).collect();
+//! assert_eq!(vec, [0, 1, 2]);
+//! ```
+//!
+//! [`push`]: SharedVec::push
+//! [`reserve`]: SharedVec::reserve
+//! [`insert`]: SharedVec::insert
+//! [`reserve_exact`]: SharedVec::reserve_exact
+//! [`new`]: SharedVec::new
+//! [`with_capacity`]: SharedVec::with_capacity
+//! [`with_growth_policy`]: SharedVec::with_growth_policy
+
+#[cfg(doc)]
+use crate::{SharedSmallVec, SharedVec, SharedVecCursor, SharedVecDeque};
+
+/// Compute the new capacity when the buffer needs to grow
+///
+/// The result less than `required` is ignored, `required` is used instead
+pub trait GrowthPolicy {
+    /// Returns the new capacity of `T` for the buffer of `capacity`,
+    /// that needs at least `required` capacity
+    ///
+    /// Never called for zero sized `T`
+    fn grow<T>(capacity: usize, required: usize) -> usize;
+}
+
+/// The minimum non-zero capacity, same as `Vec`
+const fn min_non_zero_cap<T>() -> usize {
+    match size_of::<T>() {
+        1 => 8,
+        0..=1024 => 4,
+        _ => 1,
+    }
+}
+
+/// Double the capacity, the default policy, same as `Vec`
+#[derive(Debug)]
+pub enum Doubling {}
+
+impl GrowthPolicy for Doubling {
+    fn grow<T>(capacity: usize, required: usize) -> usize {
+        capacity.saturating_mul(2)
+            .max(required)
+            .max(min_non_zero_cap::<T>())
+    }
+}
+
+/// Grow the capacity by half, wastes less memory for large buffers
+#[derive(Debug)]
+pub enum OneAndHalf {}
+
+impl GrowthPolicy for OneAndHalf {
+    fn grow<T>(capacity: usize, required: usize) -> usize {
+        capacity.saturating_add(capacity / 2)
+            .max(required)
+            .max(min_non_zero_cap::<T>())
+    }
+}
+
+/// Grow the capacity by `N` elements, or multiple of `N` to reach the required capacity
+///
+/// Pushing is not amortized `O(1)`, prefer [`reserve`](SharedVec::reserve)
+#[derive(Debug)]
+pub enum FixedIncrement<const N: usize> {}
+
+impl<const N: usize> GrowthPolicy for FixedIncrement<N> {
+    fn grow<T>(capacity: usize, required: usize) -> usize {
+        let increment = N.max(1);
+        let additional = required.saturating_sub(capacity).max(1);
+        let additional = additional.div_ceil(increment).saturating_mul(increment);
+        capacity.saturating_add(additional)
+    }
+}
+
+/// Grow the capacity by half, and round up the allocation size to whole pages of 4KiB
+///
+/// Large buffers use the space left at the end of the last page
+#[derive(Debug)]
+pub enum PageRounded {}
+
+impl PageRounded {
+    pub const PAGE_SIZE: usize = 4096;
+}
+
+impl GrowthPolicy for PageRounded {
+    fn grow<T>(capacity: usize, required: usize) -> usize {
+        let cap = OneAndHalf::grow::<T>(capacity, required);
+
+        // the reference counts before the data, see `raw::alloc_guard`
+        let header = size_of::<[usize; 2]>().max(align_of::<T>());
+        let Some(bytes) = cap.checked_mul(size_of::<T>())
+            .and_then(|bytes| bytes.checked_add(header))
+            .and_then(|bytes| bytes.checked_next_multiple_of(Self::PAGE_SIZE))
+        else {
+            return cap;
+        };
+
+        (bytes - header) / size_of::<T>()
+    }
+}
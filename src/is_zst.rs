@@ -6,6 +6,7 @@ impl<T> IsZst for T {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use core::convert::Infallible;
     use super::*;
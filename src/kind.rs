@@ -0,0 +1,153 @@
+//! Generic over [`Rc`](alloc::rc::Rc) and [`Arc`](alloc::sync::Arc)
+//!
+//! # Examples
+//!
+//! ```
+//! use rc_vec::{kind, RcKind, SharedVec, rc_vec, arc_vec};
+//!
+//! fn sum<K: RcKind>(vec: &SharedVec<u8, K>) -> u32 {
+//!     vec.iter().map(|&n| u32::from(n)).sum()
+//! }
+//!
+//! let rc_vec: SharedVec<u8, kind::Rc> = rc_vec![1, 2, 3];
+//! let arc_vec: SharedVec<u8, kind::Arc> = arc_vec![4, 5];
+//! assert_eq!(sum(&rc_vec), 6);
+//! assert_eq!(sum(&arc_vec), 9);
+//! ```
+
+use alloc::{rc, sync};
+use core::{
+    cell::Cell,
+    ops::{Deref, DerefMut},
+    sync::atomic::AtomicUsize,
+};
+use unique_rc::{UniqArc, UniqRc};
+
+use crate::{count::StrongCount, raw};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Marker type of [`Rc`](alloc::rc::Rc), the kind of [`RcVec`](crate::RcVec)
+#[derive(Debug)]
+pub enum Rc {}
+
+/// Marker type of [`Arc`](alloc::sync::Arc), the kind of [`ArcVec`](crate::ArcVec)
+#[derive(Debug)]
+pub enum Arc {}
+
+/// The kind of the reference counting pointer, implemented by [`Rc`] and [`Arc`]
+///
+/// This trait is sealed, the allocation of the pointer must be
+/// compatible with the standard library
+pub trait RcKind: sealed::Sealed + Sized + 'static {
+    /// The shared pointer, e.g [`alloc::rc::Rc`]
+    type Rc<T: ?Sized>: Clone + Deref<Target = T>;
+
+    /// The unique pointer, e.g [`UniqRc`]
+    type UniqRc<T: ?Sized>: DerefMut<Target = T>;
+
+    #[doc(hidden)]
+    fn uniq_into_raw<T: ?Sized>(this: Self::UniqRc<T>) -> *mut T;
+
+    /// # Safety
+    /// - Compliant with the safety of [`UniqRc::from_raw_unchecked`]
+    #[doc(hidden)]
+    unsafe fn uniq_from_raw<T: ?Sized>(raw: *mut T) -> Self::UniqRc<T>;
+
+    #[doc(hidden)]
+    fn uniq_try_new<T: ?Sized>(rc: Self::Rc<T>) -> Result<Self::UniqRc<T>, Self::Rc<T>>;
+
+    #[doc(hidden)]
+    fn uniq_into_rc<T: ?Sized>(this: Self::UniqRc<T>) -> Self::Rc<T>;
+
+    #[doc(hidden)]
+    fn uniq_slice_from_iter<T, I>(iter: I) -> Self::UniqRc<[T]>
+    where I: Iterator<Item = T>;
+
+    #[doc(hidden)]
+    fn rc_as_ptr<T: ?Sized>(this: &Self::Rc<T>) -> *const T;
+
+    #[doc(hidden)]
+    fn rc_get_mut<T: ?Sized>(this: &mut Self::Rc<T>) -> Option<&mut T>;
+
+    #[doc(hidden)]
+    fn rc_strong_count<T: ?Sized>(this: &Self::Rc<T>) -> usize;
+
+    /// Drop `this`, returns the unique pointer if it is the last owner,
+    /// like [`Arc::into_inner`](alloc::sync::Arc::into_inner) but for slices
+    ///
+    /// # Safety
+    /// - No `Weak` of `this` exists
+    #[doc(hidden)]
+    unsafe fn rc_slice_into_uniq<T>(this: Self::Rc<[T]>) -> Option<Self::UniqRc<[T]>>;
+}
+
+macro_rules! impl_kind {
+    ($Kind:ident, $Rc:ident, $UniqRc:ident, $rc:ident, $Count:ty) => {
+        impl sealed::Sealed for $Kind {}
+
+        impl RcKind for $Kind {
+            type Rc<T: ?Sized> = $rc::$Rc<T>;
+            type UniqRc<T: ?Sized> = $UniqRc<T>;
+
+            #[inline]
+            fn uniq_into_raw<T: ?Sized>(this: Self::UniqRc<T>) -> *mut T {
+                $UniqRc::into_raw(this)
+            }
+
+            #[inline]
+            unsafe fn uniq_from_raw<T: ?Sized>(raw: *mut T) -> Self::UniqRc<T> {
+                $UniqRc::from_raw_unchecked(raw)
+            }
+
+            #[inline]
+            fn uniq_try_new<T: ?Sized>(
+                rc: Self::Rc<T>,
+            ) -> Result<Self::UniqRc<T>, Self::Rc<T>> {
+                $UniqRc::try_new(rc)
+            }
+
+            #[inline]
+            fn uniq_into_rc<T: ?Sized>(this: Self::UniqRc<T>) -> Self::Rc<T> {
+                $UniqRc::into_rc(this)
+            }
+
+            #[inline]
+            fn uniq_slice_from_iter<T, I>(iter: I) -> Self::UniqRc<[T]>
+            where I: Iterator<Item = T>,
+            {
+                $UniqRc::from_iter(iter)
+            }
+
+            #[inline]
+            fn rc_as_ptr<T: ?Sized>(this: &Self::Rc<T>) -> *const T {
+                $rc::$Rc::as_ptr(this)
+            }
+
+            #[inline]
+            fn rc_get_mut<T: ?Sized>(this: &mut Self::Rc<T>) -> Option<&mut T> {
+                $rc::$Rc::get_mut(this)
+            }
+
+            #[inline]
+            fn rc_strong_count<T: ?Sized>(this: &Self::Rc<T>) -> usize {
+                $rc::$Rc::strong_count(this)
+            }
+
+            unsafe fn rc_slice_into_uniq<T>(
+                this: Self::Rc<[T]>,
+            ) -> Option<Self::UniqRc<[T]>> {
+                let raw = $rc::$Rc::into_raw(this).cast_mut();
+                unsafe {
+                    let count = &*raw::strong_count_ptr(raw.cast::<T>()).cast::<$Count>();
+                    count.release().then(|| $UniqRc::from_raw_unchecked(raw))
+                }
+            }
+        }
+    };
+}
+
+impl_kind!(Rc, Rc, UniqRc, rc, Cell<usize>);
+impl_kind!(Arc, Arc, UniqArc, sync, AtomicUsize);
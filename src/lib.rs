@@ -1,18 +1,40 @@
 #![doc = include_str!("../README.md")]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "nightly", feature(allocator_api))]
 
 extern crate alloc;
 
+pub mod allocator;
+mod count;
+mod error;
+pub mod growth;
+pub mod kind;
 mod raw;
 mod is_zst;
 mod rc_vec;
+mod rc_slice;
+mod rc_string;
 mod utils;
 
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod serde_as_bytes;
+
+#[cfg(feature = "stats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+pub mod stats;
+
+pub use error::*;
+pub use growth::GrowthPolicy;
+pub use kind::RcKind;
 pub use rc_vec::*;
+pub use rc_slice::*;
+pub use rc_string::*;
 pub use unique_rc;
 
-#[cfg(test)]
+// only the loom models run with `--cfg loom`, see `count`
+#[cfg(all(test, not(loom)))]
 mod tests;
 
 /// Like `vec![]`, create a [`RcVec`]
@@ -40,10 +62,10 @@ macro_rules! rc_vec {
         $crate::RcVec::new()
     };
     ($elem:expr; $n:expr) => {
-        $crate::RcVec::from_elem($elem, $n)
+        <$crate::RcVec<_>>::from_elem($elem, $n)
     };
     ($($t:tt)*) => {
-        $crate::RcVec::from_array([$($t)*])
+        <$crate::RcVec<_>>::from_array([$($t)*])
     };
 }
 
@@ -72,9 +94,9 @@ macro_rules! arc_vec {
         $crate::ArcVec::new()
     };
     ($elem:expr; $n:expr) => {
-        $crate::ArcVec::from_elem($elem, $n)
+        <$crate::ArcVec<_>>::from_elem($elem, $n)
     };
     ($($t:tt)*) => {
-        $crate::ArcVec::from_array([$($t)*])
+        <$crate::ArcVec<_>>::from_array([$($t)*])
     };
 }
@@ -1,51 +1,167 @@
-use crate::is_zst::IsZst as _;
-use core::{cmp::max, mem::{replace, MaybeUninit}, num::NonZeroUsize, ops::Range, ptr};
-
-use alloc::{rc::Rc, sync::Arc};
-use rc_vec_proc_macro::rc_impl_gen_arc_impl;
-use unique_rc::{UniqRc, UniqArc};
-
-#[rc_impl_gen_arc_impl]
-pub struct RcRawVec<T> {
-    ptr: Option<UniqRc<[MaybeUninit<T>]>>,
+use crate::{
+    allocator::{Allocator, Global},
+    growth::{Doubling, GrowthPolicy},
+    is_zst::IsZst as _,
+    RcKind, TryReserveError, TryReserveErrorKind,
+};
+use core::{
+    alloc::Layout,
+    cmp::max,
+    marker::PhantomData,
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::Range,
+    ptr::{self, NonNull},
+};
+
+use alloc::alloc::handle_alloc_error;
+
+/// The buffer is always a unique `Rc<[MaybeUninit<T>]>` allocation,
+/// allocated from `alloc`, see [`alloc_guard`]
+pub struct RawVec<T, K: RcKind, A: Allocator = Global, G: GrowthPolicy = Doubling> {
+    /// Data pointer of the allocation, `None` if never allocated
+    ptr: Option<NonNull<[MaybeUninit<T>]>>,
+    alloc: A,
+    _marker: PhantomData<K::UniqRc<[MaybeUninit<T>]>>,
+    _policy: PhantomData<fn() -> G>,
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> RcRawVec<T> {
-    pub(crate) const MIN_NON_ZERO_CAP: usize = match size_of::<T>() {
-        1 => 8,
-        0..=1024 => 4,
-        _ => 1,
-    };
-
-    pub fn take(&mut self) -> Self {
-        Self { ptr: self.ptr.take() }
-    }
-
+impl<T, K: RcKind, G: GrowthPolicy> RawVec<T, K, Global, G> {
     pub const fn new() -> Self {
-        Self { ptr: None }
+        Self::new_in(Global)
     }
 
-    pub fn from_uniq_slice(raw: UniqRc<[T]>) -> Self {
-        let raw_ptr = UniqRc::into_raw(raw);
+    pub fn from_uniq_slice(raw: K::UniqRc<[T]>) -> Self {
+        let raw_ptr = K::uniq_into_raw(raw);
         let raw_ptr = ptr::slice_from_raw_parts_mut(
             raw_ptr.cast(),
             raw_ptr.len(),
         );
-        let raw = unsafe { UniqRc::from_raw_unchecked(raw_ptr) };
-        Self { ptr: Some(raw) }
+        Self {
+            ptr: NonNull::new(raw_ptr),
+            alloc: Global,
+            _marker: PhantomData,
+            _policy: PhantomData,
+        }
     }
 
     #[inline]
-    pub fn from_raw_uniq_slice(raw: UniqRc<[MaybeUninit<T>]>) -> Self {
-        Self { ptr: Some(raw) }
+    pub fn from_raw_uniq_slice(raw: K::UniqRc<[MaybeUninit<T>]>) -> Self {
+        Self {
+            ptr: NonNull::new(K::uniq_into_raw(raw)),
+            alloc: Global,
+            _marker: PhantomData,
+            _policy: PhantomData,
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, Global)
+    }
+
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        Self::try_with_capacity_in(capacity, Global)
+    }
+
+    pub fn into_raw_rc(self) -> Option<K::UniqRc<[MaybeUninit<T>]>> {
+        let this = ManuallyDrop::new(self);
+        this.ptr.map(|ptr| unsafe { K::uniq_from_raw(ptr.as_ptr()) })
+    }
+
+    /// 对于从未分配或者ZST, 这可能创建新分配
+    pub fn into_rc(self) -> K::UniqRc<[MaybeUninit<T>]> {
+        let (ptr, Global) = self.into_raw_parts();
+        unsafe { K::uniq_from_raw(ptr.as_ptr()) }
+    }
+}
+
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> RawVec<T, K, A, G> {
+    pub const fn new_in(alloc: A) -> Self {
+        Self { ptr: None, alloc, _marker: PhantomData, _policy: PhantomData }
+    }
+
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self::try_with_capacity_in(capacity, alloc)
+            .unwrap_or_else(|e| handle_error(e))
+    }
+
+    pub fn try_with_capacity_in(
+        capacity: usize,
+        alloc: A,
+    ) -> Result<Self, TryReserveError> {
+        alloc_guard::<T>(capacity)?;
+
+        let mut this = Self::new_in(alloc);
+        if capacity != 0 && !T::ZST {
+            this.ptr = Some(this.allocate(capacity)?);
+        }
+        Ok(this)
+    }
+
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Allocate a new buffer with the reference counts of an unique `Rc`
+    fn allocate(
+        &self,
+        capacity: usize,
+    ) -> Result<NonNull<[MaybeUninit<T>]>, TryReserveError> {
+        let (layout, offset) = alloc_guard::<T>(capacity)?;
+        let rc_box = self.alloc.allocate(layout)
+            .map_err(|_| TryReserveErrorKind::AllocError { layout })?
+            .cast::<u8>();
+
+        #[cfg(feature = "stats")]
+        crate::stats::record_allocation();
+
+        unsafe {
+            // strong and weak count
+            rc_box.cast::<[usize; 2]>().write([1, 1]);
+            let data = rc_box.add(offset).cast::<MaybeUninit<T>>();
+            Ok(NonNull::slice_from_raw_parts(data, capacity))
+        }
+    }
+
+    /// Get the data pointer and allocator,
+    /// for never allocated or ZST, this may create a new allocation
+    pub fn into_raw_parts(self) -> (NonNull<[MaybeUninit<T>]>, A) {
+        let ptr = self.ptr.unwrap_or_else(|| {
+            self.allocate(0).unwrap_or_else(|e| handle_error(e))
+        });
+        let this = ManuallyDrop::new(self);
+        let alloc = unsafe { ptr::read(&this.alloc) };
+        (ptr, alloc)
+    }
+
+    /// Reuse the buffer for `U`
+    ///
+    /// # Safety
+    /// - The layout of `T` and `U` are the same
+    /// - The buffer does not contain initialized `T`
+    pub unsafe fn cast<U>(self) -> RawVec<U, K, A, G> {
+        debug_assert_eq!(Layout::new::<T>(), Layout::new::<U>());
+
+        let this = ManuallyDrop::new(self);
+        let ptr = this.ptr.map(|ptr| {
+            NonNull::slice_from_raw_parts(ptr.cast(), ptr.len())
+        });
+        let alloc = unsafe { ptr::read(&this.alloc) };
+
+        RawVec { ptr, alloc, _marker: PhantomData, _policy: PhantomData }
+    }
+
+    /// Use the growth policy `G2` for the same buffer
+    pub fn with_policy<G2: GrowthPolicy>(self) -> RawVec<T, K, A, G2> {
+        let this = ManuallyDrop::new(self);
+        let alloc = unsafe { ptr::read(&this.alloc) };
+        RawVec { ptr: this.ptr, alloc, _marker: PhantomData, _policy: PhantomData }
     }
 
     #[inline]
     pub fn as_ptr(&self) -> *const T {
-        if let Some(ptr) = self.ptr.as_ref() {
-            let rc = unsafe { UniqRc::get_rc_unchecked(ptr) };
-            Rc::as_ptr(rc).cast()
+        if let Some(ptr) = self.ptr {
+            ptr.as_ptr().cast_const().cast()
         } else {
             ptr::dangling()
         }
@@ -57,59 +173,32 @@ impl<T> RcRawVec<T> {
     }
 
     pub fn slice(&self) -> &[MaybeUninit<T>] {
-        self.ptr.as_deref()
-            .unwrap_or_default()
+        self.ptr.map_or(&[], |ptr| unsafe { ptr.as_ref() })
     }
 
     pub fn slice_mut(&mut self) -> &mut [MaybeUninit<T>] {
-        self.ptr.as_deref_mut()
-            .unwrap_or_default()
-    }
-
-    pub fn with_capacity(capacity: usize) -> Self {
-        alloc_guard(capacity);
-
-        NonZeroUsize::new(capacity).map_or(Self::new(), |cap| {
-            let iter = (0..cap.into()).map(|_| {
-                MaybeUninit::uninit()
-            });
-            let ptr = UniqRc::from_iter(iter);
-            debug_assert_eq!(ptr.len(), capacity);
-            Self { ptr: Some(ptr) }
-        })
+        self.ptr.map_or(&mut [], |mut ptr| unsafe { ptr.as_mut() })
     }
 
     pub fn capacity(&self) -> usize {
         if T::ZST {
             usize::MAX
-        } else if self.ptr.is_some() {
-            self.ptr.as_deref()
-                .map_or(0, |ptr| ptr.len())
         } else {
-            0
+            self.ptr.map_or(0, |ptr| ptr.len())
         }
     }
 
-    pub fn into_raw_rc(self) -> Option<UniqRc<[MaybeUninit<T>]>> {
-        self.ptr
-    }
-
-    /// 对于从未分配或者ZST, 这可能创建新分配
-    pub fn into_rc(self) -> UniqRc<[MaybeUninit<T>]> {
-        self.into_raw_rc().unwrap_or_else(|| {
-            unsafe { UniqRc::new_unchecked(Rc::new([])) }
-        })
-    }
-
     #[inline]
     pub fn reserve(&mut self, len: usize, additional: usize) {
         #[cold]
-        fn reserve_cold<T>(
-            this: &mut RcRawVec<T>,
+        fn reserve_cold<T, K: RcKind, A: Allocator, G: GrowthPolicy>(
+            this: &mut RawVec<T, K, A, G>,
             len: usize,
             additional: usize,
         ) {
-            this.grow_amortized(len, additional);
+            if let Err(e) = this.grow_amortized(len, additional) {
+                handle_error(e)
+            }
         }
         if self.needs_to_grow(len, additional) {
             reserve_cold(self, len, additional);
@@ -118,13 +207,37 @@ impl<T> RcRawVec<T> {
 
     #[inline(never)]
     pub fn reserve_for_push(&mut self, len: usize) {
-        self.grow_amortized(len, 1);
+        if let Err(e) = self.grow_amortized(len, 1) {
+            handle_error(e)
+        }
     }
 
     pub fn reserve_exact(&mut self, len: usize, additional: usize) {
+        if let Err(e) = self.try_reserve_exact(len, additional) {
+            handle_error(e)
+        }
+    }
+
+    pub fn try_reserve(
+        &mut self,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
         if self.needs_to_grow(len, additional) {
-            self.grow_exact(len, additional);
+            self.grow_amortized(len, additional)?;
         }
+        Ok(())
+    }
+
+    pub fn try_reserve_exact(
+        &mut self,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
+        if self.needs_to_grow(len, additional) {
+            self.grow_exact(len, additional)?;
+        }
+        Ok(())
     }
 
     pub fn drop_elems(&mut self, len: usize) {
@@ -132,7 +245,7 @@ impl<T> RcRawVec<T> {
     }
 
     pub fn drop_elems_from_range(&mut self, range: Range<usize>) {
-        if self.ptr.is_none() {
+        if self.ptr.is_none() && !T::ZST {
             return;
         }
         let len = range.len();
@@ -150,73 +263,196 @@ impl<T> RcRawVec<T> {
     }
 
 
-    fn grow_amortized(&mut self, len: usize, additional: usize) {
+    fn grow_amortized(
+        &mut self,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
         debug_assert_ne!(additional, 0);
 
-        if T::ZST { return }
+        if T::ZST {
+            // capacity is usize::MAX, so getting here means overflow
+            return Err(TryReserveErrorKind::CapacityOverflow.into());
+        }
 
-        let required_cap = len.saturating_add(additional);
+        let required_cap = len.checked_add(additional)
+            .ok_or(TryReserveErrorKind::CapacityOverflow)?;
 
-        let cap = max(self.capacity().saturating_mul(2), required_cap);
-        let cap = max(Self::MIN_NON_ZERO_CAP, cap);
+        let cap = max(G::grow::<T>(self.capacity(), required_cap), required_cap);
 
-        let old = replace(self, Self::with_capacity(cap));
-        unsafe {
-            let src = old.as_ptr();
-            ptr::copy_nonoverlapping(src, self.as_mut_ptr(), len);
-        }
+        self.grow_to(len, cap)
     }
 
-    fn grow_exact(&mut self, len: usize, additional: usize) {
+    fn grow_exact(
+        &mut self,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
         debug_assert_ne!(additional, 0);
 
-        if T::ZST { return }
+        if T::ZST {
+            return Err(TryReserveErrorKind::CapacityOverflow.into());
+        }
+
+        let cap = len.checked_add(additional)
+            .ok_or(TryReserveErrorKind::CapacityOverflow)?;
+
+        self.grow_to(len, cap)
+    }
 
-        let cap = len.saturating_add(additional);
+    fn grow_to(&mut self, len: usize, cap: usize) -> Result<(), TryReserveError> {
+        if self.ptr.is_some() {
+            self.realloc(len, cap)
+        } else {
+            debug_assert_eq!(len, 0);
+            self.ptr = Some(self.allocate(cap)?);
+            Ok(())
+        }
+    }
+
+    /// Resize the unique allocation to `cap` elements,
+    /// the `len` initialized elements are moved by the allocator
+    ///
+    /// The allocation is still a valid [`Rc`](alloc::rc::Rc) allocation,
+    /// see [`alloc_guard`]
+    fn realloc(&mut self, len: usize, cap: usize) -> Result<(), TryReserveError> {
+        debug_assert!(!T::ZST);
+        debug_assert_ne!(cap, 0);
+        debug_assert!(len <= cap);
+
+        let Some(old) = self.ptr else { unreachable!() };
+        let old_cap = old.len();
+
+        let (new_layout, offset) = alloc_guard::<T>(cap)?;
+        let Ok((old_layout, old_offset)) = alloc_guard::<T>(old_cap) else {
+            unreachable!()
+        };
+        debug_assert_eq!(offset, old_offset);
 
-        let old = replace(self, Self::with_capacity(cap));
         unsafe {
-            let src = old.as_ptr();
-            ptr::copy_nonoverlapping(src, self.as_mut_ptr(), len);
+            let old_rc_box = old.cast::<u8>().sub(offset);
+            let rc_box = if cap > old_cap {
+                self.alloc.grow(old_rc_box, old_layout, new_layout)
+            } else {
+                self.alloc.shrink(old_rc_box, old_layout, new_layout)
+            };
+            let rc_box = rc_box.map_err(|_| TryReserveErrorKind::AllocError {
+                layout: new_layout,
+            })?;
+
+            #[cfg(feature = "stats")]
+            crate::stats::record(|stats| {
+                stats.reallocations = stats.reallocations.wrapping_add(1);
+                // the allocator may resize in place without copy
+                if rc_box.cast::<u8>() != old_rc_box {
+                    let copied = len * size_of::<T>();
+                    stats.copied_bytes = stats.copied_bytes.wrapping_add(copied);
+                }
+            });
+
+            let data = rc_box.cast::<u8>().add(offset).cast::<MaybeUninit<T>>();
+            self.ptr = Some(NonNull::slice_from_raw_parts(data, cap));
         }
+        Ok(())
     }
 
-    pub fn shrink_to_fit(&mut self, cap: usize) {
+    pub fn shrink_to_fit(&mut self, len: usize, cap: usize) {
         assert!(cap <= self.capacity());
 
-        if T::ZST { return }
+        if T::ZST || self.ptr.is_none() { return }
+
+        if cap == 0 {
+            self.deallocate();
+        } else if let Err(e) = self.realloc(len, cap) {
+            handle_error(e)
+        }
+    }
+
+    fn deallocate(&mut self) {
+        let Some(ptr) = self.ptr.take() else { return };
+        let Ok((layout, offset)) = alloc_guard::<T>(ptr.len()) else {
+            unreachable!()
+        };
 
-        let old = replace(self, Self::with_capacity(cap));
         unsafe {
-            let src = old.as_ptr();
-            ptr::copy_nonoverlapping(src, self.as_mut_ptr(), cap);
+            let rc_box = ptr.cast::<u8>().sub(offset);
+            self.alloc.deallocate(rc_box, layout);
         }
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> Default for RcRawVec<T> {
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> Drop for RawVec<T, K, A, G> {
+    fn drop(&mut self) {
+        self.deallocate();
+    }
+}
+
+impl<T, K: RcKind, G: GrowthPolicy> Default for RawVec<T, K, Global, G> {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Get the allocation layout and data offset of `Rc<[T]>` with `capacity` elements
+///
+/// The allocation of [`Rc`](alloc::rc::Rc) and [`Arc`](alloc::sync::Arc)
+/// is a `#[repr(C)]` box,
+/// two reference counts are followed by the data
+///
+/// # Safety invariant
+///
+/// Every allocation of this module relies on the private layout of
+/// `RcBox` and `ArcInner` in the standard library,
+/// which is not documented or guaranteed:
+///
+/// - The header is the strong and weak count, layout of `[usize; 2]`
+/// - The data follows at the offset of `Layout::extend`,
+///   and the size is padded to the alignment
+/// - An unique pointer has the counts `[1, 1]`, the weak count includes the strong owners
+/// - `Rc<[T]>` and `Arc<[T]>` are allocated and deallocated by [`Global`]
+///   with exactly this layout
+///
+/// So the buffer is allocated, reallocated and deallocated here,
+/// and converted from or into `Rc<[T]>` without copy,
+/// the test `std_layout` checks it against the standard library
+#[inline]
+fn alloc_guard<T>(capacity: usize) -> Result<(Layout, usize), TryReserveError> {
+    Layout::array::<T>(capacity)
+        .and_then(|array| Layout::new::<[usize; 2]>().extend(array))
+        .map(|(layout, offset)| (layout.pad_to_align(), offset))
+        .map_err(|_| TryReserveErrorKind::CapacityOverflow.into())
+}
+
+/// Get the strong count in the header of the `Rc<[T]>` allocation of `data`
+///
+/// # Safety
+/// - `data` is the data pointer of an `Rc<[T]>` or `Arc<[T]>`, see [`alloc_guard`]
+#[inline]
+pub unsafe fn strong_count_ptr<T>(data: *const T) -> *const usize {
+    let Ok((_, offset)) = alloc_guard::<T>(0) else { unreachable!() };
+    unsafe { data.cast::<u8>().sub(offset).cast() }
+}
+
 #[cold]
 #[inline(never)]
-fn alloc_guard(alloc_size: usize) {
-    if usize::BITS < 64 && alloc_size > isize::MAX as usize {
-        panic!("allocate size {alloc_size} overflow")
+#[track_caller]
+fn handle_error(e: TryReserveError) -> ! {
+    match e.kind() {
+        TryReserveErrorKind::CapacityOverflow => panic!("capacity overflow"),
+        TryReserveErrorKind::AllocError { layout } => handle_alloc_error(layout),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloc::borrow::ToOwned;
+    use crate::kind;
+    use alloc::{borrow::ToOwned, rc::Rc, sync::Arc};
+    use unique_rc::{UniqArc, UniqRc};
 
     #[test]
     fn it_works() {
-        let mut raw = RcRawVec::with_capacity(3);
+        let mut raw = RawVec::<_, kind::Rc>::with_capacity(3);
         assert_eq!(raw.capacity(), 3);
         raw.slice_mut()[0].write("a".to_owned());
         raw.slice_mut()[1].write("b".to_owned());
@@ -236,4 +472,105 @@ mod tests {
 
         raw.drop_elems(3);
     }
+
+    #[test]
+    fn realloc_layout() {
+        #[repr(align(32))]
+        #[derive(Debug, PartialEq)]
+        struct Align32(u8);
+
+        let rc: Rc<[Align32]> = Rc::new([Align32(1), Align32(2)]);
+        let mut raw = RawVec::<_, kind::Rc>::from_uniq_slice(UniqRc::try_new(rc).unwrap());
+        raw.reserve_exact(2, 7);
+        raw.slice_mut()[8].write(Align32(3));
+
+        let data = raw.into_rc();
+        assert_eq!(data.len(), 9);
+        let rc = UniqRc::into_rc(data);
+        let weak = Rc::downgrade(&rc);
+        let cloned = rc.clone();
+        assert_eq!(Rc::strong_count(&rc), 2);
+        assert_eq!(Rc::weak_count(&rc), 1);
+        unsafe {
+            assert_eq!(cloned[0].assume_init_ref(), &Align32(1));
+            assert_eq!(cloned[1].assume_init_ref(), &Align32(2));
+            assert_eq!(cloned[8].assume_init_ref(), &Align32(3));
+        }
+        drop((rc, cloned));
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn std_layout() {
+        #[repr(align(8))]
+        #[derive(Clone, Copy)]
+        struct Align8(#[allow(unused)] u8);
+
+        #[repr(align(16))]
+        #[derive(Clone, Copy)]
+        struct Align16(#[allow(unused)] u8);
+
+        #[repr(align(32))]
+        #[derive(Clone, Copy)]
+        struct Align32(#[allow(unused)] u8);
+
+        /// Read the counts at the computed offset before the data
+        unsafe fn counts<T>(data: *const T) -> [usize; 2] {
+            let Ok((_, offset)) = alloc_guard::<T>(3) else { unreachable!() };
+            unsafe { data.cast::<u8>().sub(offset).cast::<[usize; 2]>().read() }
+        }
+
+        fn check<T: Copy>(value: T) {
+            let Ok((layout, offset)) = alloc_guard::<T>(3) else { unreachable!() };
+            assert_eq!(offset % align_of::<T>(), 0);
+            assert_eq!(layout.align(), align_of::<[usize; 2]>().max(align_of::<T>()));
+
+            let rc: Rc<[T]> = Rc::new([value; 3]);
+            assert_eq!(unsafe { counts(Rc::as_ptr(&rc).cast::<T>()) }, [1, 1]);
+            let cloned = rc.clone();
+            let weak = Rc::downgrade(&rc);
+            assert_eq!(unsafe { counts(Rc::as_ptr(&rc).cast::<T>()) }, [2, 2]);
+            drop((cloned, weak));
+
+            // deallocated by `RawVec` with the computed layout
+            let raw = RawVec::<_, kind::Rc>::from_uniq_slice(UniqRc::try_new(rc).ok().unwrap());
+            drop(raw);
+
+            let arc: Arc<[T]> = Arc::new([value; 3]);
+            assert_eq!(unsafe { counts(Arc::as_ptr(&arc).cast::<T>()) }, [1, 1]);
+            let cloned = arc.clone();
+            assert_eq!(unsafe { counts(Arc::as_ptr(&arc).cast::<T>()) }, [2, 1]);
+            drop(cloned);
+
+            let raw = RawVec::<_, kind::Arc>::from_uniq_slice(UniqArc::try_new(arc).ok().unwrap());
+            drop(raw);
+
+            // allocated by `RawVec`, read by `Rc`
+            let mut raw = RawVec::<T, kind::Rc>::with_capacity(3);
+            raw.slice_mut().iter_mut().for_each(|elem| { elem.write(value); });
+            let rc = UniqRc::into_rc(raw.into_rc());
+            let weak = Rc::downgrade(&rc);
+            assert_eq!(Rc::strong_count(&rc), 1);
+            assert_eq!(Rc::weak_count(&rc), 1);
+            drop(rc);
+            assert!(weak.upgrade().is_none());
+        }
+
+        check(1u8);
+        check(Align8(1));
+        check(Align16(1));
+        check(Align32(1));
+    }
+
+    #[test]
+    fn realloc_arc_shrink() {
+        let arc: Arc<[u16]> = Arc::new([1, 2, 3, 4]);
+        let mut raw = RawVec::<_, kind::Arc>::from_uniq_slice(UniqArc::new(arc));
+        raw.shrink_to_fit(3, 3);
+        assert_eq!(raw.capacity(), 3);
+
+        let arc = UniqArc::into_rc(raw.into_rc());
+        assert_eq!(arc.len(), 3);
+        assert_eq!(unsafe { arc[2].assume_init() }, 3);
+    }
 }
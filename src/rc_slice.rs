@@ -0,0 +1,216 @@
+use alloc::{rc::Rc, sync::Arc};
+use core::{
+    fmt::{self, Debug},
+    hash::{self, Hash},
+    ops::{Deref, RangeBounds},
+    slice,
+};
+use rc_vec_proc_macro::rc_impl_gen_arc_impl;
+
+use crate::{kind, utils, RcKind, SharedVec};
+
+/// Cheaply cloneable sub-range view of a frozen `K::Rc<[T]>`,
+/// like `bytes::Bytes::slice`
+///
+/// Generic over the [`RcKind`], usually used through [`RcSlice`] or [`ArcSlice`]
+///
+/// # Examples
+///
+/// ```
+/// # use rc_vec::{rc_vec, RcSlice};
+/// let all = RcSlice::from(rc_vec![1, 2, 3, 4, 5]);
+/// let mid = all.slice(1..4);
+/// assert_eq!(mid, [2, 3, 4]);
+///
+/// let (a, b) = mid.split_at(1);
+/// assert_eq!(a, [2]);
+/// assert_eq!(b, [3, 4]);
+/// assert_eq!(b.as_ptr(), all[2..].as_ptr());
+///
+/// drop((a, b, mid));
+/// let vec = all.try_into_rc_vec().unwrap();
+/// assert_eq!(vec, [1, 2, 3, 4, 5]);
+/// ```
+pub struct SharedSlice<T, K: RcKind> {
+    rc: K::Rc<[T]>,
+    offset: usize,
+    len: usize,
+}
+
+/// [`SharedSlice`] based on [`Rc`]
+pub type RcSlice<T> = SharedSlice<T, kind::Rc>;
+
+/// [`SharedSlice`] based on [`Arc`]
+pub type ArcSlice<T> = SharedSlice<T, kind::Arc>;
+
+impl<T, K: RcKind> Clone for SharedSlice<T, K> {
+    fn clone(&self) -> Self {
+        Self { rc: self.rc.clone(), offset: self.offset, len: self.len }
+    }
+}
+
+impl<T, K: RcKind> Default for SharedSlice<T, K> {
+    fn default() -> Self {
+        Self::new(SharedVec::<T, K>::new().into_rc_slice())
+    }
+}
+
+impl<T, K: RcKind> Deref for SharedSlice<T, K> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe {
+            let data = K::rc_as_ptr(&self.rc).cast::<T>().add(self.offset);
+            slice::from_raw_parts(data, self.len)
+        }
+    }
+}
+
+impl<T, K: RcKind> SharedSlice<T, K> {
+    /// Create a view of the whole `rc`
+    pub fn new(rc: K::Rc<[T]>) -> Self {
+        let len = rc.len();
+        Self { rc, offset: 0, len }
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    /// The underlying whole buffer
+    #[inline]
+    pub fn as_rc(&self) -> &K::Rc<[T]> {
+        &self.rc
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a view of the `range` of this view, share the same buffer
+    ///
+    /// # Panics
+    /// - `range` out of bounds
+    #[track_caller]
+    pub fn slice<R>(&self, range: R) -> Self
+    where R: RangeBounds<usize>,
+    {
+        let range = utils::range(range, ..self.len);
+        Self {
+            rc: self.rc.clone(),
+            offset: self.offset + range.start,
+            len: range.len(),
+        }
+    }
+
+    /// Like `<[T]>::split_at`, but returns two views
+    ///
+    /// # Panics
+    /// - `mid > len`
+    #[track_caller]
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        assert!(mid <= self.len, "mid > len");
+        (self.slice(..mid), self.slice(mid..))
+    }
+
+    /// Returns `true` if the view covers the whole buffer
+    #[inline]
+    pub fn is_whole(&self) -> bool {
+        self.offset == 0 && self.len == self.rc.len()
+    }
+
+    /// Convert into [`SharedVec`] without copy, by [`SharedVec::try_from_rc`]
+    ///
+    /// # Errors
+    /// - The view does not cover the whole buffer
+    /// - The buffer is shared
+    pub fn try_into_rc_vec(self) -> Result<SharedVec<T, K>, Self> {
+        if !self.is_whole() {
+            return Err(self);
+        }
+
+        let Self { rc, offset, len } = self;
+        SharedVec::try_from_rc(rc).map_err(|rc| Self { rc, offset, len })
+    }
+
+    /// Convert into [`SharedVec`], clone the elements
+    /// when the view cannot be converted without copy
+    pub fn into_rc_vec(self) -> SharedVec<T, K>
+    where T: Clone,
+    {
+        self.try_into_rc_vec().unwrap_or_else(|this| {
+            #[cfg(feature = "stats")]
+            crate::stats::record_conversion(false);
+
+            this.as_slice().into()
+        })
+    }
+}
+
+
+impl<T, K: RcKind> From<SharedVec<T, K>> for SharedSlice<T, K> {
+    /// Freeze `value` by [`SharedVec::into_rc_slice`]
+    fn from(value: SharedVec<T, K>) -> Self {
+        Self::new(value.into_rc_slice())
+    }
+}
+
+impl<T: Debug, K: RcKind> Debug for SharedSlice<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: Hash, K: RcKind> Hash for SharedSlice<T, K> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+impl<T, K: RcKind> AsRef<[T]> for SharedSlice<T, K> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: Eq, K: RcKind> Eq for SharedSlice<T, K> {}
+
+impl<T: PartialEq, K: RcKind> PartialEq for SharedSlice<T, K> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: PartialEq, K: RcKind> PartialEq<[T]> for SharedSlice<T, K> {
+    fn eq(&self, other: &[T]) -> bool {
+        **self == *other
+    }
+}
+
+impl<T: PartialEq, K: RcKind, const N: usize> PartialEq<[T; N]> for SharedSlice<T, K> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        **self == *other
+    }
+}
+
+impl<T: PartialEq, K: RcKind> PartialEq<&[T]> for SharedSlice<T, K> {
+    fn eq(&self, other: &&[T]) -> bool {
+        **self == **other
+    }
+}
+
+// `K::Rc<[T]>` cannot be used in the generic impls, it may overlap with the other impls,
+// these are implemented for each kind, use `SharedSlice::new` instead
+#[rc_impl_gen_arc_impl]
+impl<T> From<Rc<[T]>> for RcSlice<T> {
+    fn from(value: Rc<[T]>) -> Self {
+        Self::new(value)
+    }
+}
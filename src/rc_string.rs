@@ -0,0 +1,652 @@
+use alloc::{rc::Rc, string::String, sync::Arc};
+#[cfg(doc)]
+use crate::{ArcVec, RcVec};
+use core::{
+    borrow::{Borrow, BorrowMut},
+    fmt::{self, Debug, Display},
+    hash::{self, Hash},
+    iter::FusedIterator,
+    ops::{Deref, DerefMut, Range, RangeBounds},
+    ptr::{self, NonNull},
+    str::{self, Chars, Utf8Error},
+};
+use rc_vec_proc_macro::rc_impl_gen_arc_impl;
+use unique_rc::{UniqArc, UniqRc};
+
+use crate::{kind, utils, RcKind, SharedVec};
+
+/// A possible error value when converting a byte vector into a string
+///
+/// Like [`alloc::string::FromUtf8Error`], but keeps the original vector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FromUtf8Error<V> {
+    bytes: V,
+    error: Utf8Error,
+}
+
+impl<V> FromUtf8Error<V> {
+    /// Returns the bytes that were attempted to convert
+    pub fn into_bytes(self) -> V {
+        self.bytes
+    }
+
+    /// Returns the bytes that were attempted to convert
+    pub fn as_bytes(&self) -> &V {
+        &self.bytes
+    }
+
+    /// Fetch a [`Utf8Error`] to get more details about the conversion failure
+    pub fn utf8_error(&self) -> Utf8Error {
+        self.error
+    }
+}
+
+impl<V> Display for FromUtf8Error<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl<V: Debug> core::error::Error for FromUtf8Error<V> {}
+
+/// UTF-8 checked owned string based on [`SharedVec<u8, K>`],
+/// and can be converted from and into [`Rc<str>`] without allocation
+///
+/// Generic over the [`RcKind`], usually used through [`RcString`] or [`ArcString`]
+///
+/// # Examples
+///
+/// ```
+/// # use std::rc::Rc;
+/// use rc_vec::RcString;
+///
+/// let rc: Rc<str> = Rc::from("foo");
+/// let mut s = RcString::from(rc);
+///
+/// s.push_str("bar");
+/// assert_eq!(s, "foobar");
+///
+/// let rc: Rc<str> = s.into_rc_str();
+/// assert_eq!(&*rc, "foobar");
+/// ```
+pub struct SharedString<K: RcKind> {
+    vec: SharedVec<u8, K>,
+}
+
+/// [`SharedString`] based on [`Rc`], the string of [`RcVec<u8>`]
+pub type RcString = SharedString<kind::Rc>;
+
+/// [`SharedString`] based on [`Arc`], the string of [`ArcVec<u8>`]
+pub type ArcString = SharedString<kind::Arc>;
+
+impl<K: RcKind> Default for SharedString<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: RcKind> Clone for SharedString<K> {
+    fn clone(&self) -> Self {
+        Self { vec: self.vec.clone() }
+    }
+}
+
+impl<K: RcKind> Eq for SharedString<K> {}
+
+impl<K: RcKind> PartialEq for SharedString<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.vec == other.vec
+    }
+}
+
+impl<K: RcKind> PartialOrd for SharedString<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: RcKind> Ord for SharedString<K> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.vec.cmp(&other.vec)
+    }
+}
+
+impl<K: RcKind> Deref for SharedString<K> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { str::from_utf8_unchecked(&self.vec) }
+    }
+}
+
+impl<K: RcKind> DerefMut for SharedString<K> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { str::from_utf8_unchecked_mut(&mut self.vec) }
+    }
+}
+
+impl<K: RcKind> SharedString<K> {
+    /// Create a new empty [`SharedString`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::RcString;
+    /// let mut s = RcString::new();
+    /// s.push_str("foo");
+    /// assert_eq!(s, "foo");
+    /// ```
+    pub fn new() -> Self {
+        Self { vec: SharedVec::new() }
+    }
+
+    /// Create a new empty [`SharedString`] Initial capacity of `capacity` bytes
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { vec: SharedVec::with_capacity(capacity) }
+    }
+
+    /// Like [`String::from_utf8`]
+    ///
+    /// # Errors
+    /// - `vec` is not valid UTF-8, the original vector is returned in the error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::{RcString, rc_vec};
+    /// let s = RcString::from_utf8(rc_vec![b'a', b'b']).unwrap();
+    /// assert_eq!(s, "ab");
+    ///
+    /// let err = RcString::from_utf8(rc_vec![b'a', 0xff]).unwrap_err();
+    /// assert_eq!(err.utf8_error().valid_up_to(), 1);
+    /// assert_eq!(err.into_bytes(), [b'a', 0xff]);
+    /// ```
+    pub fn from_utf8(vec: SharedVec<u8, K>) -> Result<Self, FromUtf8Error<SharedVec<u8, K>>> {
+        match str::from_utf8(&vec) {
+            Ok(_) => Ok(Self { vec }),
+            Err(error) => Err(FromUtf8Error { bytes: vec, error }),
+        }
+    }
+
+    /// Like [`String::from_utf8_unchecked`]
+    ///
+    /// # Safety
+    /// - `vec` must be valid UTF-8
+    #[inline]
+    pub unsafe fn from_utf8_unchecked(vec: SharedVec<u8, K>) -> Self {
+        Self { vec }
+    }
+
+    /// Convert into the underlying byte vector
+    #[inline]
+    pub fn into_bytes(self) -> SharedVec<u8, K> {
+        self.vec
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self
+    }
+
+    #[inline]
+    pub fn as_mut_str(&mut self) -> &mut str {
+        self
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.vec
+    }
+
+    /// Like [`String::as_mut_vec`]
+    ///
+    /// # Safety
+    /// - The contents must be valid UTF-8 when the borrow ends
+    #[inline]
+    pub unsafe fn as_mut_vec(&mut self) -> &mut SharedVec<u8, K> {
+        &mut self.vec
+    }
+
+    /// Length in bytes
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// Allocated capacity in bytes
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.vec.reserve(additional);
+    }
+
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.vec.reserve_exact(additional);
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.vec.shrink_to_fit();
+    }
+
+    pub fn push_str(&mut self, string: &str) {
+        self.vec.extend_from_copy_slice(string.as_bytes());
+    }
+
+    pub fn push(&mut self, ch: char) {
+        match ch.len_utf8() {
+            1 => self.vec.push(ch as u8),
+            _ => self.push_str(ch.encode_utf8(&mut [0; 4])),
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<char> {
+        let ch = self.chars().next_back()?;
+        let new_len = self.len() - ch.len_utf8();
+        unsafe { self.vec.set_len(new_len) }
+        Some(ch)
+    }
+
+    /// Like [`String::truncate`]
+    ///
+    /// # Panics
+    /// - `new_len` does not lie on a [`char`] boundary
+    #[track_caller]
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len <= self.len() {
+            assert!(self.is_char_boundary(new_len),
+                    "new_len (is {new_len}) should be on char boundary");
+            self.vec.truncate(new_len);
+        }
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.vec.clear();
+    }
+
+    /// Like [`String::remove`]
+    ///
+    /// # Panics
+    /// - `idx` is larger than or equal to the length,
+    ///   or it does not lie on a [`char`] boundary
+    #[track_caller]
+    pub fn remove(&mut self, idx: usize) -> char {
+        let Some(ch) = self[idx..].chars().next() else {
+            panic!("cannot remove a char from the end of a string");
+        };
+
+        let next = idx + ch.len_utf8();
+        let len = self.len();
+        unsafe {
+            let ptr = self.vec.as_mut_ptr();
+            ptr::copy(ptr.add(next), ptr.add(idx), len - next);
+            self.vec.set_len(len - (next - idx));
+        }
+        ch
+    }
+
+    /// Like [`String::insert`]
+    ///
+    /// # Panics
+    /// - `idx` is larger than the length, or it does not lie on a [`char`] boundary
+    #[track_caller]
+    pub fn insert(&mut self, idx: usize, ch: char) {
+        self.insert_str(idx, ch.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Like [`String::insert_str`]
+    ///
+    /// # Panics
+    /// - `idx` is larger than the length, or it does not lie on a [`char`] boundary
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::RcString;
+    /// let mut s = RcString::from("bar");
+    /// s.insert_str(0, "foo");
+    /// assert_eq!(s, "foobar");
+    /// ```
+    #[track_caller]
+    pub fn insert_str(&mut self, idx: usize, string: &str) {
+        assert!(self.is_char_boundary(idx),
+                "insertion index (is {idx}) should be on char boundary");
+
+        let len = self.len();
+        let amt = string.len();
+        self.vec.reserve(amt);
+
+        unsafe {
+            let ptr = self.vec.as_mut_ptr();
+            ptr::copy(ptr.add(idx), ptr.add(idx + amt), len - idx);
+            ptr::copy_nonoverlapping(string.as_ptr(), ptr.add(idx), amt);
+            self.vec.set_len(len + amt);
+        }
+    }
+
+    /// Like [`String::split_off`]
+    ///
+    /// # Panics
+    /// - `at` does not lie on a [`char`] boundary
+    #[track_caller]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(self.is_char_boundary(at),
+                "`at` split index (is {at}) should be on char boundary");
+        Self { vec: self.vec.split_off(at) }
+    }
+
+    /// Like [`String::drain`]
+    ///
+    /// # Panics
+    /// - The starting point or end point do not lie on a [`char`] boundary,
+    ///   or they're out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::RcString;
+    /// let mut s = RcString::from("α is alpha");
+    /// let beta_offset = s.find(' ').unwrap();
+    ///
+    /// let t: String = s.drain(..beta_offset).collect();
+    /// assert_eq!(t, "α");
+    /// assert_eq!(s, " is alpha");
+    /// ```
+    #[track_caller]
+    pub fn drain<R>(&mut self, range: R) -> SharedStringDrain<'_, K>
+    where R: RangeBounds<usize>,
+    {
+        let Range { start, end } = utils::range(range, ..self.len());
+        assert!(self.is_char_boundary(start));
+        assert!(self.is_char_boundary(end));
+
+        let string = NonNull::from(&mut *self);
+        let chars = unsafe { self.get_unchecked(start..end) }.chars();
+
+        SharedStringDrain { start, end, iter: chars, string }
+    }
+
+    /// Convert from [`UniqRc<str>`] or [`UniqArc<str>`] without copy
+    pub fn from_uniq_str(value: K::UniqRc<str>) -> Self {
+        let raw = K::uniq_into_raw(value) as *mut [u8];
+        let slice = unsafe { K::uniq_from_raw(raw) };
+        Self { vec: SharedVec::from_uniq_slice(slice) }
+    }
+
+    /// Convert from [`Rc<str>`] or [`Arc<str>`],
+    /// copy the string only when it is shared
+    pub fn from_rc_str(value: K::Rc<str>) -> Self {
+        match K::uniq_try_new(value) {
+            Ok(uniq) => Self::from_uniq_str(uniq),
+            Err(rc) => Self::from(&*rc),
+        }
+    }
+
+    /// Shrink and convert into [`UniqRc<str>`] or [`UniqArc<str>`]
+    #[inline]
+    pub fn into_uniq_str(self) -> K::UniqRc<str> {
+        let slice = self.vec.into_uniq_slice();
+        let raw = K::uniq_into_raw(slice) as *mut str;
+        unsafe { K::uniq_from_raw(raw) }
+    }
+
+    /// Shrink and convert into [`Rc<str>`] or [`Arc<str>`],
+    /// no copy when `len == capacity`
+    #[inline]
+    pub fn into_rc_str(self) -> K::Rc<str> {
+        K::uniq_into_rc(self.into_uniq_str())
+    }
+}
+
+/// A draining iterator for [`SharedString`], like [`alloc::string::Drain`]
+pub struct SharedStringDrain<'a, K: RcKind> {
+    string: NonNull<SharedString<K>>,
+    start: usize,
+    end: usize,
+    iter: Chars<'a>,
+}
+
+/// [`SharedStringDrain`] of [`RcString`]
+pub type RcStringDrain<'a> = SharedStringDrain<'a, kind::Rc>;
+
+/// [`SharedStringDrain`] of [`ArcString`]
+pub type ArcStringDrain<'a> = SharedStringDrain<'a, kind::Arc>;
+
+impl<K: RcKind> Debug for SharedStringDrain<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedStringDrain")
+            .field(&self.as_str())
+            .finish()
+    }
+}
+
+impl<K: RcKind> SharedStringDrain<'_, K> {
+    pub fn as_str(&self) -> &str {
+        self.iter.as_str()
+    }
+}
+
+impl<K: RcKind> Drop for SharedStringDrain<'_, K> {
+    fn drop(&mut self) {
+        unsafe {
+            let vec = self.string.as_mut().as_mut_vec();
+            vec.drain(self.start..self.end);
+        }
+    }
+}
+
+impl<K: RcKind> Iterator for SharedStringDrain<'_, K> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<K: RcKind> DoubleEndedIterator for SharedStringDrain<'_, K> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<K: RcKind> FusedIterator for SharedStringDrain<'_, K> { }
+
+impl<K: RcKind> AsRef<str> for SharedString<K> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<K: RcKind> AsMut<str> for SharedString<K> {
+    fn as_mut(&mut self) -> &mut str {
+        self
+    }
+}
+
+impl<K: RcKind> AsRef<[u8]> for SharedString<K> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<K: RcKind> Borrow<str> for SharedString<K> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<K: RcKind> BorrowMut<str> for SharedString<K> {
+    fn borrow_mut(&mut self) -> &mut str {
+        self
+    }
+}
+
+impl<K: RcKind> Debug for SharedString<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<K: RcKind> Display for SharedString<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+impl<K: RcKind> Hash for SharedString<K> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+impl<K: RcKind> fmt::Write for SharedString<K> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.push(c);
+        Ok(())
+    }
+}
+
+impl<K: RcKind> PartialEq<str> for SharedString<K> {
+    fn eq(&self, other: &str) -> bool {
+        **self == *other
+    }
+}
+
+impl<K: RcKind> PartialEq<&str> for SharedString<K> {
+    fn eq(&self, other: &&str) -> bool {
+        **self == **other
+    }
+}
+
+impl<K: RcKind> PartialEq<String> for SharedString<K> {
+    fn eq(&self, other: &String) -> bool {
+        **self == **other
+    }
+}
+
+impl<K: RcKind> PartialEq<SharedString<K>> for str {
+    fn eq(&self, other: &SharedString<K>) -> bool {
+        *self == **other
+    }
+}
+
+impl<K: RcKind> PartialEq<SharedString<K>> for &str {
+    fn eq(&self, other: &SharedString<K>) -> bool {
+        **self == **other
+    }
+}
+
+impl<K: RcKind> PartialEq<SharedString<K>> for String {
+    fn eq(&self, other: &SharedString<K>) -> bool {
+        **self == **other
+    }
+}
+
+impl<K: RcKind> From<&str> for SharedString<K> {
+    fn from(value: &str) -> Self {
+        Self { vec: value.into() }
+    }
+}
+
+impl<K: RcKind> From<char> for SharedString<K> {
+    fn from(value: char) -> Self {
+        Self::from(&*value.encode_utf8(&mut [0; 4]))
+    }
+}
+
+// `K::Rc<str>` cannot be used in the generic impls, it may overlap with the other impls,
+// these are implemented for each kind, use the generic methods instead,
+// e.g `SharedString::from_rc_str`
+#[rc_impl_gen_arc_impl]
+impl From<UniqRc<str>> for RcString {
+    fn from(value: UniqRc<str>) -> Self {
+        Self::from_uniq_str(value)
+    }
+}
+
+#[rc_impl_gen_arc_impl]
+impl From<Rc<str>> for RcString {
+    fn from(value: Rc<str>) -> Self {
+        Self::from_rc_str(value)
+    }
+}
+
+#[rc_impl_gen_arc_impl]
+impl From<RcString> for Rc<str> {
+    fn from(value: RcString) -> Self {
+        value.into_rc_str()
+    }
+}
+
+impl<K: RcKind> From<SharedString<K>> for SharedVec<u8, K> {
+    fn from(value: SharedString<K>) -> Self {
+        value.into_bytes()
+    }
+}
+
+impl<K: RcKind> TryFrom<SharedVec<u8, K>> for SharedString<K> {
+    type Error = FromUtf8Error<SharedVec<u8, K>>;
+
+    fn try_from(value: SharedVec<u8, K>) -> Result<Self, Self::Error> {
+        Self::from_utf8(value)
+    }
+}
+
+impl<K: RcKind> FromIterator<char> for SharedString<K> {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let mut buf = Self::new();
+        buf.extend(iter);
+        buf
+    }
+}
+
+impl<'a, K: RcKind> FromIterator<&'a str> for SharedString<K> {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut buf = Self::new();
+        buf.extend(iter);
+        buf
+    }
+}
+
+impl<K: RcKind> Extend<char> for SharedString<K> {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        iter.for_each(|ch| self.push(ch));
+    }
+}
+
+impl<'a, K: RcKind> Extend<&'a char> for SharedString<K> {
+    fn extend<I: IntoIterator<Item = &'a char>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<'a, K: RcKind> Extend<&'a str> for SharedString<K> {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|s| self.push_str(s));
+    }
+}
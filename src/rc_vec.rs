@@ -1,11 +1,12 @@
 #[cfg(doc)]
 use alloc::{boxed::Box, vec::Vec};
-
+#[cfg(any(doc, feature = "nightly"))]
 use alloc::{rc::Rc, sync::Arc};
+
 use core::{
     cmp::max,
     iter,
-    mem::MaybeUninit,
+    mem::{self, ManuallyDrop, MaybeUninit},
     ops::{Deref, DerefMut, Range, RangeBounds},
     ptr, slice,
 };
@@ -13,18 +14,39 @@ use rc_vec_proc_macro::rc_impl_gen_arc_impl;
 use unique_rc::{UniqArc, UniqRc};
 
 use crate::{
-    raw::{ArcRawVec, RcRawVec},
-    utils,
+    allocator::{Allocator, Global},
+    growth::{Doubling, GrowthPolicy},
+    is_zst::IsZst as _,
+    kind,
+    raw::RawVec,
+    utils, RcKind, TryPushError, TryReserveError,
 };
 
+mod cow;
+mod cursor;
+mod deque;
 mod drain;
+#[cfg(feature = "rayon")]
+mod par_iter;
+mod small;
 mod trait_impls;
 
+pub use cow::*;
+pub use cursor::*;
+pub use deque::*;
 pub use drain::*;
+pub use small::*;
+pub use trait_impls::into_iter::*;
+
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+pub use par_iter::*;
 
 /// [`RcVec`] based on [`Rc`] and can be converted from Rc without allocation,
 /// just like [`Box`] is converted to [`Vec`]
 ///
+/// Generic over the [`RcKind`], usually used through [`RcVec`] or [`ArcVec`]
+///
 /// # Examples
 ///
 /// ```
@@ -38,14 +60,18 @@ pub use drain::*;
 /// rc_vec.push(4);
 /// assert_eq!(rc_vec, [1, 2, 3, 4]);
 /// ```
-#[rc_impl_gen_arc_impl]
-pub struct RcVec<T> {
-    raw: RcRawVec<T>,
+pub struct SharedVec<T, K: RcKind, A: Allocator = Global, G: GrowthPolicy = Doubling> {
+    raw: RawVec<T, K, A, G>,
     len: usize,
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> Deref for RcVec<T> {
+/// [`SharedVec`] based on [`Rc`]
+pub type RcVec<T, A = Global, G = Doubling> = SharedVec<T, kind::Rc, A, G>;
+
+/// [`SharedVec`] based on [`Arc`]
+pub type ArcVec<T, A = Global, G = Doubling> = SharedVec<T, kind::Arc, A, G>;
+
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> Deref for SharedVec<T, K, A, G> {
     type Target = [T];
 
     fn deref(&self) -> &Self::Target {
@@ -54,46 +80,46 @@ impl<T> Deref for RcVec<T> {
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> DerefMut for RcVec<T> {
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> DerefMut for SharedVec<T, K, A, G> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         let ptr = self.raw.as_mut_ptr();
         unsafe { slice::from_raw_parts_mut(ptr, self.len) }
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> Drop for RcVec<T> {
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> Drop for SharedVec<T, K, A, G> {
     fn drop(&mut self) {
         self.raw.drop_elems(self.len);
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: Clone> Clone for RcVec<T> {
+// The buffer is uniquely owned, like `Vec<T>`
+unsafe impl<T: Send, A: Allocator + Send, G: GrowthPolicy> Send for ArcVec<T, A, G> { }
+unsafe impl<T: Sync, A: Allocator + Sync, G: GrowthPolicy> Sync for ArcVec<T, A, G> { }
+
+impl<T: Clone, K: RcKind, A: Allocator + Clone, G: GrowthPolicy> Clone for SharedVec<T, K, A, G> {
     fn clone(&self) -> Self {
-        self.as_slice().into()
+        let mut vec = SharedVec::with_capacity_in(self.len(), self.allocator().clone())
+            .with_growth_policy();
+        vec.extend_from_slice(self);
+        vec
     }
 }
 
 #[rc_impl_gen_arc_impl]
 impl<T> From<UniqRc<[T]>> for RcVec<T> {
     fn from(value: UniqRc<[T]>) -> Self {
-        let len = value.len();
-        let raw = RcRawVec::from_uniq_slice(value);
-        Self { raw, len }
+        Self::from_uniq_slice(value)
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> Default for RcVec<T> {
+impl<T, K: RcKind, G: GrowthPolicy> Default for SharedVec<T, K, Global, G> {
     fn default() -> Self {
-        Self::new()
+        Self { raw: RawVec::new(), len: 0 }
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> RcVec<T> {
+impl<T, K: RcKind> SharedVec<T, K> {
     /// Create a new [`RcVec`]
     ///
     /// # Examples
@@ -105,7 +131,7 @@ impl<T> RcVec<T> {
     /// assert_eq!(vec, [3]);
     /// ```
     pub fn new() -> Self {
-        Self { raw: RcRawVec::new(), len: 0 }
+        Self { raw: RawVec::new(), len: 0 }
     }
 
     /// Create a new [`RcVec`] Initial capacity of `capacity`
@@ -120,7 +146,211 @@ impl<T> RcVec<T> {
     /// # assert_ne!(vec, [1]);
     /// ```
     pub fn with_capacity(capacity: usize) -> Self {
-        Self { raw: RcRawVec::with_capacity(capacity), len: 0 }
+        Self { raw: RawVec::with_capacity(capacity), len: 0 }
+    }
+
+    /// Like [`with_capacity`](#method.with_capacity), but returns an error on overflow
+    ///
+    /// # Errors
+    /// - The capacity exceeds `isize::MAX` bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::RcVec;
+    /// let vec = RcVec::<u8>::try_with_capacity(16).unwrap();
+    /// assert_eq!(vec.capacity(), 16);
+    ///
+    /// assert!(RcVec::<u64>::try_with_capacity(usize::MAX).is_err());
+    /// ```
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        Ok(Self { raw: RawVec::try_with_capacity(capacity)?, len: 0 })
+    }
+
+    /// # Safety
+    /// `slice` `0..len` must be initialized
+    #[inline]
+    pub unsafe fn from_raw_uniq_slice(
+        slice: K::UniqRc<[MaybeUninit<T>]>,
+        len: usize,
+    ) -> Self {
+        Self { raw: RawVec::from_raw_uniq_slice(slice), len }
+    }
+
+    pub fn from_uniq_slice(slice: K::UniqRc<[T]>) -> Self {
+        let len = slice.len();
+        let raw = RawVec::from_uniq_slice(slice);
+        Self { raw, len }
+    }
+
+    /// Reuse the allocation of `rc` without copy
+    ///
+    /// # Errors
+    /// - `rc` is shared, `strong_count != 1` or any `Weak` exists
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::RcVec;
+    /// # use std::rc::Rc;
+    /// let rc: Rc<[i32]> = Rc::new([1, 2, 3]);
+    /// let shared = rc.clone();
+    ///
+    /// let rc = RcVec::try_from_rc(rc).unwrap_err();
+    /// drop(shared);
+    ///
+    /// let vec = RcVec::try_from_rc(rc).unwrap();
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    pub fn try_from_rc(rc: K::Rc<[T]>) -> Result<Self, K::Rc<[T]>> {
+        let vec = K::uniq_try_new(rc).map(Self::from_uniq_slice)?;
+
+        #[cfg(feature = "stats")]
+        crate::stats::record_conversion(true);
+
+        Ok(vec)
+    }
+
+    /// Like `RcVec::from(rc)`, and returns `true` when
+    /// the allocation of `rc` is reused without clone
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::RcVec;
+    /// # use std::rc::Rc;
+    /// let rc: Rc<[i32]> = Rc::new([1, 2, 3]);
+    /// let shared = rc.clone();
+    ///
+    /// let (vec, reused) = RcVec::from_rc_or_clone(rc);
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// assert!(! reused);
+    ///
+    /// let (vec, reused) = RcVec::from_rc_or_clone(shared);
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// assert!(reused);
+    /// ```
+    pub fn from_rc_or_clone(rc: K::Rc<[T]>) -> (Self, bool)
+    where T: Clone,
+    {
+        match Self::try_from_rc(rc) {
+            Ok(vec) => (vec, true),
+            Err(rc) => {
+                #[cfg(feature = "stats")]
+                crate::stats::record_conversion(false);
+
+                ((*rc).into(), false)
+            },
+        }
+    }
+
+}
+
+impl<T, K: RcKind, G: GrowthPolicy> SharedVec<T, K, Global, G> {
+    #[inline]
+    pub fn into_raw_uniq_slice(self) -> K::UniqRc<[MaybeUninit<T>]> {
+        self.into_raw_vec().into_rc()
+    }
+
+    #[inline]
+    pub fn into_raw_uniq_slice_optional(self) -> Option<K::UniqRc<[MaybeUninit<T>]>> {
+        self.into_raw_vec().into_raw_rc()
+    }
+
+    #[inline]
+    pub fn into_uniq_slice(mut self) -> K::UniqRc<[T]> {
+        self.shrink_to_fit();
+        let len = self.len();
+        debug_assert!(T::ZST || len == self.capacity());
+
+        let (raw, Global) = self.into_raw_vec().into_raw_parts();
+        let slice = ptr::slice_from_raw_parts_mut(raw.as_ptr().cast::<T>(), len);
+
+        #[cfg(feature = "stats")]
+        crate::stats::record_conversion(true);
+
+        unsafe { K::uniq_from_raw(slice) }
+    }
+
+    #[inline]
+    pub fn into_rc_slice(self) -> K::Rc<[T]> {
+        K::uniq_into_rc(self.into_uniq_slice())
+    }
+
+    /// Like [`into_rc_slice`](#method.into_rc_slice), but into an array
+    ///
+    /// # Errors
+    /// - `len != N`, returns the original vector
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::rc_vec;
+    /// # use std::rc::Rc;
+    /// let rc: Rc<[i32; 3]> = rc_vec![1, 2, 3].try_into_rc_array().unwrap();
+    /// assert_eq!(*rc, [1, 2, 3]);
+    ///
+    /// let vec = rc_vec![1, 2].try_into_rc_array::<3>().unwrap_err();
+    /// assert_eq!(vec, [1, 2]);
+    /// ```
+    pub fn try_into_rc_array<const N: usize>(self) -> Result<K::Rc<[T; N]>, Self> {
+        if self.len() != N {
+            return Err(self);
+        }
+
+        let raw = K::uniq_into_raw(self.into_uniq_slice()).cast::<[T; N]>();
+        Ok(K::uniq_into_rc(unsafe { K::uniq_from_raw(raw) }))
+    }
+}
+
+impl<T, K: RcKind, A: Allocator> SharedVec<T, K, A> {
+    /// Like [`new`](#method.new), but with the allocator `alloc`
+    pub const fn new_in(alloc: A) -> Self {
+        Self { raw: RawVec::new_in(alloc), len: 0 }
+    }
+
+    /// Like [`with_capacity`](#method.with_capacity), but with the allocator `alloc`
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self { raw: RawVec::with_capacity_in(capacity, alloc), len: 0 }
+    }
+
+    /// Like [`try_with_capacity`](#method.try_with_capacity),
+    /// but with the allocator `alloc`
+    ///
+    /// # Errors
+    /// - The capacity exceeds `isize::MAX` bytes
+    /// - The allocator returned an error
+    pub fn try_with_capacity_in(
+        capacity: usize,
+        alloc: A,
+    ) -> Result<Self, TryReserveError> {
+        Ok(Self { raw: RawVec::try_with_capacity_in(capacity, alloc)?, len: 0 })
+    }
+}
+
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> SharedVec<T, K, A, G> {
+    /// Use the growth policy `G2` for the same buffer, without copy
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rc_vec::{growth::FixedIncrement, RcVec};
+    ///
+    /// let mut vec = RcVec::with_capacity(4)
+    ///     .with_growth_policy::<FixedIncrement<16>>();
+    /// vec.extend([0; 5]);
+    /// assert_eq!(vec.capacity(), 20);
+    /// ```
+    #[inline]
+    pub fn with_growth_policy<G2: GrowthPolicy>(self) -> SharedVec<T, K, A, G2> {
+        let len = self.len;
+        SharedVec { raw: self.into_raw_vec().with_policy(), len }
+    }
+
+    /// Returns a reference to the underlying allocator
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.raw.allocator()
     }
 
     /// Readonly permission pointer
@@ -243,6 +473,65 @@ impl<T> RcVec<T> {
         self.raw.reserve_exact(self.len, additional);
     }
 
+    /// Like [`Vec::try_reserve`]
+    ///
+    /// # Errors
+    /// - The new capacity overflows or exceeds `isize::MAX` bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::rc_vec;
+    /// let mut vec = rc_vec![1, 2];
+    /// vec.try_reserve(8).unwrap();
+    /// assert!(vec.capacity() >= 10);
+    ///
+    /// assert!(vec.try_reserve(usize::MAX).is_err());
+    /// assert_eq!(vec, [1, 2]);
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.raw.try_reserve(self.len, additional)
+    }
+
+    /// Like [`Vec::try_reserve_exact`]
+    ///
+    /// # Errors
+    /// - The new capacity overflows or exceeds `isize::MAX` bytes
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.raw.try_reserve_exact(self.len, additional)
+    }
+
+    /// Like [`push`](#method.push), but returns an error instead of panicking
+    /// when the capacity cannot grow
+    ///
+    /// # Errors
+    /// - The new capacity overflows or exceeds `isize::MAX` bytes,
+    ///   `value` is returned in the error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::RcVec;
+    /// let mut vec = RcVec::new();
+    /// vec.try_push(1).unwrap();
+    /// assert_eq!(vec, [1]);
+    /// ```
+    #[inline]
+    pub fn try_push(&mut self, value: T) -> Result<(), TryPushError<T>> {
+        if self.len == self.capacity() {
+            if let Err(e) = self.raw.try_reserve(self.len, 1) {
+                return Err(TryPushError::new(value, e));
+            }
+        }
+
+        unsafe {
+            let end = self.as_mut_ptr().add(self.len);
+            end.write(value);
+            self.len += 1;
+        }
+        Ok(())
+    }
+
     #[inline]
     pub fn pop(&mut self) -> Option<T> {
         if self.len == 0 {
@@ -340,6 +629,42 @@ impl<T> RcVec<T> {
         }
     }
 
+    /// Insert all elements of `iter` at position `index`,
+    /// shifting all elements after it to the right
+    ///
+    /// # Panics
+    ///
+    /// - `index > self.len()`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::rc_vec;
+    /// let mut vec = rc_vec![1, 2, 3];
+    /// vec.insert_many(1, [4, 5]);
+    /// assert_eq!(vec, [1, 4, 5, 2, 3]);
+    /// vec.insert_many(5, [6]);
+    /// assert_eq!(vec, [1, 4, 5, 2, 3, 6]);
+    /// ```
+    #[track_caller]
+    pub fn insert_many<I>(&mut self, index: usize, iter: I)
+    where I: IntoIterator<Item = T>,
+    {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("insertion index (is {index}) should be <= len (is {len})");
+        }
+
+        let len = self.len();
+        if index > len {
+            assert_failed(index, len);
+        }
+
+        self.splice(index..index, iter);
+    }
+
     #[inline]
     #[track_caller]
     pub fn swap_remove(&mut self, index: usize) -> T {
@@ -378,14 +703,14 @@ impl<T> RcVec<T> {
     /// ```
     pub fn shrink_to_fit(&mut self) {
         if self.capacity() > self.len() {
-            self.raw.shrink_to_fit(self.len());
+            self.raw.shrink_to_fit(self.len, self.len);
         }
     }
 
     /// Reallocate to max(`min_capacity`, `.len()`)
     pub fn shrink_to(&mut self, min_capacity: usize) {
         if self.capacity() > min_capacity {
-            self.raw.shrink_to_fit(max(self.len(), min_capacity));
+            self.raw.shrink_to_fit(self.len, max(self.len, min_capacity));
         }
     }
 
@@ -423,63 +748,31 @@ impl<T> RcVec<T> {
     /// ```
     #[inline]
     pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
-        &mut self.raw.slice_mut()[self.len..]
+        let spare_len = self.capacity() - self.len;
+
+        // NOTE: ZST buffer may be unallocated, but capacity is usize::MAX
+        unsafe {
+            let ptr = self.as_mut_ptr().add(self.len);
+            slice::from_raw_parts_mut(ptr.cast(), spare_len)
+        }
     }
 
     unsafe fn split_at_spare_mut_with_len(
         &mut self,
     ) -> (&mut [T], &mut [MaybeUninit<T>], &mut usize) {
-        let (initialized, spare)
-            = self.raw.slice_mut().split_at_mut(self.len);
-        let initialized_ptr = initialized.as_mut_ptr().cast::<T>();
-        let initialized = slice::from_raw_parts_mut(initialized_ptr, self.len);
-
-        (initialized, spare, &mut self.len)
-    }
-
-    /// # Safety
-    /// `slice` `0..len` must be initialized
-    #[inline]
-    pub unsafe fn from_raw_uniq_slice(
-        slice: UniqRc<[MaybeUninit<T>]>,
-        len: usize,
-    ) -> Self {
-        Self { raw: RcRawVec::from_raw_uniq_slice(slice), len }
-    }
+        let ptr = self.as_mut_ptr();
+        let spare_len = self.capacity() - self.len;
 
-    pub fn from_uniq_slice(slice: UniqRc<[T]>) -> Self {
-        slice.into()
-    }
+        let initialized = slice::from_raw_parts_mut(ptr, self.len);
+        let spare = slice::from_raw_parts_mut(ptr.add(self.len).cast(), spare_len);
 
-    #[inline]
-    pub fn into_raw_uniq_slice(mut self) -> UniqRc<[MaybeUninit<T>]> {
-        self.raw.take().into_rc()
-    }
-
-    #[inline]
-    pub fn into_raw_uniq_slice_optional(mut self) -> Option<UniqRc<[MaybeUninit<T>]>> {
-        self.raw.take().into_raw_rc()
-    }
-
-    #[inline]
-    pub fn into_uniq_slice(mut self) -> UniqRc<[T]> {
-        self.shrink_to_fit();
-        let len = self.len();
-        debug_assert_eq!(len, self.capacity());
-
-        let raw = UniqRc::into_raw(self.raw.take().into_rc());
-        let slice = ptr::slice_from_raw_parts_mut(raw.cast::<T>(), len);
-        unsafe { UniqRc::from_raw_unchecked(slice) }
-    }
-
-    #[inline]
-    pub fn into_rc_slice(self) -> Rc<[T]> {
-        self.into_uniq_slice().into()
+        (initialized, spare, &mut self.len)
     }
 
     #[inline]
-    pub(crate) fn into_raw_vec(mut self) -> RcRawVec<T> {
-        self.raw.take()
+    pub(crate) fn into_raw_vec(self) -> RawVec<T, K, A, G> {
+        let this = ManuallyDrop::new(self);
+        unsafe { ptr::read(&this.raw) }
     }
 
     pub fn truncate(&mut self, len: usize) {
@@ -513,24 +806,15 @@ impl<T> RcVec<T> {
         if new_len > len {
             self.extend(iter::repeat_with(f).take(new_len-len));
         } else {
-            self.truncate(len);
+            self.truncate(new_len);
         }
     }
 
-    pub fn leak(mut self) -> &'static mut [T] {
-        let len = self.len();
-
-        if len == 0 {
-            return Default::default();
-        }
-
-        self.raw.take()
-            .into_raw_rc()
-            .map(UniqRc::into_raw)
-            .map(|raw| {
-                unsafe { slice::from_raw_parts_mut(raw.cast::<T>(), len) }
-            })
-            .unwrap_or_default()
+    pub fn leak<'a>(self) -> &'a mut [T]
+    where A: 'a,
+    {
+        let mut this = ManuallyDrop::new(self);
+        unsafe { slice::from_raw_parts_mut(this.as_mut_ptr(), this.len) }
     }
 
     /// Like [`Vec::drain`]
@@ -548,7 +832,7 @@ impl<T> RcVec<T> {
     /// v.drain(..);
     /// assert_eq!(v, &[]);
     /// ```
-    pub fn drain<R>(&mut self, range: R) -> RcVecDrain<'_, T>
+    pub fn drain<R>(&mut self, range: R) -> SharedVecDrain<'_, T, K, A, G>
     where R: RangeBounds<usize>,
     {
         let len = self.len();
@@ -562,7 +846,7 @@ impl<T> RcVec<T> {
                 end - start,
             );
 
-            RcVecDrain {
+            SharedVecDrain {
                 tail_start: end,
                 tail_len: len - end,
                 iter: slice.iter(),
@@ -571,6 +855,70 @@ impl<T> RcVec<T> {
         }
     }
 
+    /// Like [`Vec::splice`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::{RcVec, rc_vec};
+    /// let mut v = rc_vec![1, 2, 3, 4];
+    /// let new = [7, 8, 9];
+    /// let u: RcVec<_> = v.splice(1..3, new).collect();
+    /// assert_eq!(v, [1, 7, 8, 9, 4]);
+    /// assert_eq!(u, [2, 3]);
+    /// ```
+    #[inline]
+    pub fn splice<R, I>(
+        &mut self,
+        range: R,
+        replace_with: I,
+    ) -> SharedVecSplice<'_, I::IntoIter, K, A, G>
+    where R: RangeBounds<usize>,
+          I: IntoIterator<Item = T>,
+    {
+        SharedVecSplice {
+            drain: self.drain(range),
+            replace_with: replace_with.into_iter(),
+        }
+    }
+
+    /// Like [`Vec::extract_if`]
+    ///
+    /// If the returned iterator is not exhausted,
+    /// the remaining elements will be retained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::{RcVec, rc_vec};
+    /// let mut numbers = rc_vec![1, 2, 3, 4, 5, 6, 8, 9, 11, 13, 14, 15];
+    ///
+    /// let evens = numbers.extract_if(.., |x| *x % 2 == 0).collect::<RcVec<_>>();
+    /// let odds = numbers;
+    ///
+    /// assert_eq!(evens, [2, 4, 6, 8, 14]);
+    /// assert_eq!(odds, [1, 3, 5, 9, 11, 13, 15]);
+    /// ```
+    pub fn extract_if<F, R>(&mut self, range: R, filter: F) -> SharedVecExtractIf<'_, T, F, K, A, G>
+    where F: FnMut(&mut T) -> bool,
+          R: RangeBounds<usize>,
+    {
+        let old_len = self.len();
+        let Range { start, end } = utils::range(range, ..old_len);
+
+        // Guard against the vec getting leaked (leak amplification)
+        unsafe { self.set_len(0) }
+
+        SharedVecExtractIf {
+            vec: self,
+            idx: start,
+            del: 0,
+            end,
+            old_len,
+            pred: filter,
+        }
+    }
+
     /// Like [`Vec::append`]
     ///
     /// # Examples
@@ -613,7 +961,9 @@ impl<T> RcVec<T> {
     /// assert_eq!(vec, [1]);
     /// assert_eq!(vec2, [2, 3]);
     /// ```
-    pub fn split_off(&mut self, at: usize) -> Self {
+    pub fn split_off(&mut self, at: usize) -> Self
+    where A: Clone,
+    {
         #[cold]
         #[inline(never)]
         fn assert_failed(at: usize, len: usize) -> ! {
@@ -625,7 +975,8 @@ impl<T> RcVec<T> {
         }
 
         let remainder_len = self.len() - at;
-        let mut other = Self::with_capacity(remainder_len);
+        let mut other = SharedVec::with_capacity_in(remainder_len, self.allocator().clone())
+            .with_growth_policy();
 
         unsafe {
             self.set_len(at);
@@ -682,14 +1033,14 @@ impl<T> RcVec<T> {
         let original_len = self.len();
         unsafe { self.set_len(0) };
 
-        struct ShiftGuard<'a, T> {
-            v: &'a mut RcVec<T>,
+        struct ShiftGuard<'a, T, K: RcKind, A: Allocator, G: GrowthPolicy> {
+            v: &'a mut SharedVec<T, K, A, G>,
             processed_len: usize,
             deleted_cnt: usize,
             original_len: usize,
         }
 
-        impl<T> Drop for ShiftGuard<'_, T> {
+        impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> Drop for ShiftGuard<'_, T, K, A, G> {
             fn drop(&mut self) {
                 if self.deleted_cnt > 0 {
                     // SAFETY: 尾随的未检查项必须有效，因为我们从不碰它们。
@@ -715,10 +1066,10 @@ impl<T> RcVec<T> {
             original_len,
         };
 
-        fn process_loop<F, T, const DELETED: bool>(
+        fn process_loop<F, T, K: RcKind, A: Allocator, G: GrowthPolicy, const DELETED: bool>(
             original_len: usize,
             f: &mut F,
-            g: &mut ShiftGuard<'_, T>,
+            g: &mut ShiftGuard<'_, T, K, A, G>,
         )
         where F: FnMut(&mut T) -> bool,
         {
@@ -750,21 +1101,180 @@ impl<T> RcVec<T> {
             }
         }
 
-        process_loop::<F, T, false>(original_len, &mut f, &mut g);
-        process_loop::<F, T, true>(original_len, &mut f, &mut g);
+        process_loop::<F, T, K, A, G, false>(original_len, &mut f, &mut g);
+        process_loop::<F, T, K, A, G, true>(original_len, &mut f, &mut g);
         drop(g);
     }
+
+    /// Like [`Vec::dedup_by_key`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::rc_vec;
+    /// let mut vec = rc_vec![10, 20, 21, 30, 20];
+    /// vec.dedup_by_key(|i| *i / 10);
+    /// assert_eq!(vec, [10, 20, 30, 20]);
+    /// ```
+    #[inline]
+    pub fn dedup_by_key<F, Key>(&mut self, mut key: F)
+    where F: FnMut(&mut T) -> Key,
+          Key: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Like [`Vec::dedup_by`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::rc_vec;
+    /// let mut vec = rc_vec!["foo", "bar", "Bar", "baz", "bar"];
+    /// vec.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    /// assert_eq!(vec, ["foo", "bar", "baz", "bar"]);
+    /// ```
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where F: FnMut(&mut T, &mut T) -> bool,
+    {
+        // This implement code from alloc::vec
+
+        let len = self.len();
+        if len <= 1 {
+            return;
+        }
+
+        // Check if we ever want to remove anything.
+        // This allows to use copy_non_overlapping in next cycle.
+        // And avoids any memory writes if we don't need to remove anything.
+        let mut first_duplicate_idx: usize = 1;
+        let start = self.as_mut_ptr();
+        while first_duplicate_idx != len {
+            let found_duplicate = unsafe {
+                // SAFETY: first_duplicate always in range [1..len)
+                // Note that we start iteration from 1 so we never overflow.
+                let prev = start.add(first_duplicate_idx.wrapping_sub(1));
+                let current = start.add(first_duplicate_idx);
+                // We explicitly say in docs that references are reversed.
+                same_bucket(&mut *current, &mut *prev)
+            };
+            if found_duplicate {
+                break;
+            }
+            first_duplicate_idx += 1;
+        }
+        // Don't need to remove anything.
+        // We cannot get bigger than len.
+        if first_duplicate_idx == len {
+            return;
+        }
+
+        /* INVARIANT: vec.len() > read > write > write-1 >= 0 */
+        struct FillGapOnDrop<'a, T, K: RcKind, A: Allocator, G: GrowthPolicy> {
+            /* Offset of the element we want to check if it is duplicate */
+            read: usize,
+
+            /* Offset of the place where we want to place the non-duplicate
+             * when we find it. */
+            write: usize,
+
+            /* The Vec that would need correction if `same_bucket` panicked */
+            vec: &'a mut SharedVec<T, K, A, G>,
+        }
+
+        impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> Drop for FillGapOnDrop<'_, T, K, A, G> {
+            fn drop(&mut self) {
+                /* This code gets executed when `same_bucket` panics */
+
+                /* SAFETY: invariant guarantees that `read - write`
+                 * and `len - read` never overflow and that the copy is always
+                 * in-bounds. */
+                unsafe {
+                    let ptr = self.vec.as_mut_ptr();
+                    let len = self.vec.len();
+
+                    /* How many items were left when `same_bucket` panicked.
+                     * Basically vec[read..].len() */
+                    let items_left = len.wrapping_sub(self.read);
+
+                    /* Pointer to first item in vec[write..write+items_left] slice */
+                    let dropped_ptr = ptr.add(self.write);
+                    /* Pointer to first item in vec[read..] slice */
+                    let valid_ptr = ptr.add(self.read);
+
+                    /* Copy `vec[read..]` to `vec[write..write+items_left]`.
+                     * The slices can overlap, so `copy_nonoverlapping` cannot be used */
+                    ptr::copy(valid_ptr, dropped_ptr, items_left);
+
+                    /* How many items have been already dropped
+                     * Basically vec[read..write].len() */
+                    let dropped = self.read.wrapping_sub(self.write);
+
+                    self.vec.set_len(len - dropped);
+                }
+            }
+        }
+
+        /* Drop items while going through Vec, it should be more efficient than
+         * doing slice partition_dedup + truncate */
+
+        // Construct gap first and then drop item to avoid memory corruption if `T::drop` panics.
+        let mut gap = FillGapOnDrop {
+            read: first_duplicate_idx + 1,
+            write: first_duplicate_idx,
+            vec: self,
+        };
+        unsafe {
+            // SAFETY: we checked that first_duplicate_idx in bounds before.
+            // If drop panics, `gap` would remove this item without drop.
+            ptr::drop_in_place(start.add(first_duplicate_idx));
+        }
+
+        /* SAFETY: Because of the invariant, read_ptr, prev_ptr and write_ptr
+         * are always in-bounds and read_ptr never aliases prev_ptr */
+        unsafe {
+            while gap.read < len {
+                let read_ptr = start.add(gap.read);
+                let prev_ptr = start.add(gap.write.wrapping_sub(1));
+
+                // We explicitly say in docs that references are reversed.
+                let found_duplicate = same_bucket(&mut *read_ptr, &mut *prev_ptr);
+                if found_duplicate {
+                    // Increase `gap.read` now since the drop may panic.
+                    gap.read += 1;
+                    /* We have found duplicate, drop it in-place */
+                    ptr::drop_in_place(read_ptr);
+                } else {
+                    let write_ptr = start.add(gap.write);
+
+                    /* read_ptr cannot be equal to write_ptr because at this point
+                     * we guaranteed to skip at least one element (before loop starts).
+                     */
+                    ptr::copy_nonoverlapping(read_ptr, write_ptr, 1);
+
+                    /* We have filled that place, so go further */
+                    gap.write += 1;
+                    gap.read += 1;
+                }
+            }
+
+            /* Technically we could let `gap` clean up with its Drop, but
+             * when `same_bucket` is guaranteed to not panic, this bloats a little
+             * the codegen, so we just do it manually */
+            gap.vec.set_len(gap.write);
+            mem::forget(gap);
+        }
+    }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: Clone> RcVec<T> {
+impl<T: Clone, K: RcKind, A: Allocator, G: GrowthPolicy> SharedVec<T, K, A, G> {
     pub fn resize(&mut self, new_len: usize, value: T) {
         let len = self.len();
 
         if new_len > len {
             self.extend(iter::repeat_n(value, new_len-len));
         } else {
-            self.truncate(len);
+            self.truncate(new_len);
         }
     }
 
@@ -782,6 +1292,18 @@ impl<T: Clone> RcVec<T> {
         }
     }
 
+    /// Like [`extend_from_slice`](#method.extend_from_slice),
+    /// but returns an error instead of panicking when the capacity cannot grow
+    ///
+    /// # Errors
+    /// - The new capacity overflows or exceeds `isize::MAX` bytes,
+    ///   `self` is unchanged
+    pub fn try_extend_from_slice(&mut self, buf: &[T]) -> Result<(), TryReserveError> {
+        self.try_reserve(buf.len())?;
+        self.extend_from_slice(buf);
+        Ok(())
+    }
+
     pub fn extend_from_within<R>(&mut self, src: R)
     where R: RangeBounds<usize>,
     {
@@ -800,8 +1322,89 @@ impl<T: Clone> RcVec<T> {
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> RcVec<T> {
+impl<T: Copy, K: RcKind, A: Allocator, G: GrowthPolicy> SharedVec<T, K, A, G> {
+    /// Like [`extend_from_slice`](#method.extend_from_slice),
+    /// but copy all elements at once
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::rc_vec;
+    /// let mut vec = rc_vec![1, 2];
+    /// vec.extend_from_copy_slice(&[3, 4, 5]);
+    /// assert_eq!(vec, [1, 2, 3, 4, 5]);
+    /// ```
+    pub fn extend_from_copy_slice(&mut self, buf: &[T]) {
+        let len = self.len();
+        self.reserve(buf.len());
+
+        unsafe {
+            let dst = self.as_mut_ptr().add(len);
+            ptr::copy_nonoverlapping(buf.as_ptr(), dst, buf.len());
+            self.set_len(len + buf.len());
+        }
+    }
+
+    /// Like [`extend_from_copy_slice`](#method.extend_from_copy_slice),
+    /// but returns an error instead of panicking when the capacity cannot grow
+    ///
+    /// # Errors
+    /// - The new capacity overflows or exceeds `isize::MAX` bytes,
+    ///   `self` is unchanged
+    pub fn try_extend_from_copy_slice(&mut self, buf: &[T]) -> Result<(), TryReserveError> {
+        self.try_reserve(buf.len())?;
+        self.extend_from_copy_slice(buf);
+        Ok(())
+    }
+
+    /// Like [`extend_from_within`](#method.extend_from_within),
+    /// but copy all elements at once
+    ///
+    /// # Panics
+    /// - `src` out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::rc_vec;
+    /// let mut vec = rc_vec![1, 2, 3];
+    /// vec.extend_from_copy_within(1..);
+    /// assert_eq!(vec, [1, 2, 3, 2, 3]);
+    /// ```
+    #[track_caller]
+    pub fn extend_from_copy_within<R>(&mut self, src: R)
+    where R: RangeBounds<usize>,
+    {
+        let len = self.len();
+        let range = utils::range(src, ..len);
+        self.reserve(range.len());
+
+        unsafe {
+            let ptr = self.as_mut_ptr();
+            ptr::copy_nonoverlapping(ptr.add(range.start), ptr.add(len), range.len());
+            self.set_len(len + range.len());
+        }
+    }
+}
+
+impl<T: PartialEq, K: RcKind, A: Allocator, G: GrowthPolicy> SharedVec<T, K, A, G> {
+    /// Like [`Vec::dedup`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::rc_vec;
+    /// let mut vec = rc_vec![1, 2, 2, 3, 2];
+    /// vec.dedup();
+    /// assert_eq!(vec, [1, 2, 3, 2]);
+    /// ```
+    #[inline]
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b);
+    }
+}
+
+impl<T, K: RcKind, G: GrowthPolicy> SharedVec<T, K, Global, G> {
     /// Macro support
     #[doc(hidden)]
     #[allow(unused)]
@@ -815,6 +1418,38 @@ impl<T> RcVec<T> {
     #[doc(hidden)]
     #[allow(unused)]
     pub fn from_array<const N: usize>(arr: [T; N]) -> Self {
-        arr.into()
+        Self::from_iter(arr)
+    }
+}
+
+#[rc_impl_gen_arc_impl]
+#[cfg(feature = "nightly")]
+#[cfg_attr(docsrs, doc(cfg(feature = "nightly")))]
+impl<T, A: Allocator, G: GrowthPolicy> RcVec<T, A, G> {
+    /// Like [`into_rc_slice`](#method.into_rc_slice), but keep the allocator
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    /// # use rc_vec::RcVec;
+    /// use std::{alloc::System, rc::Rc};
+    ///
+    /// let mut vec = RcVec::new_in(System);
+    /// vec.extend([1, 2, 3]);
+    /// let rc: Rc<[i32], System> = vec.into_rc_slice_in();
+    /// assert_eq!(*rc, [1, 2, 3]);
+    /// ```
+    pub fn into_rc_slice_in(mut self) -> Rc<[T], A> {
+        self.shrink_to_fit();
+        let len = self.len();
+
+        let (raw, alloc) = self.into_raw_vec().into_raw_parts();
+        let slice = ptr::slice_from_raw_parts_mut(raw.as_ptr().cast::<T>(), len);
+
+        #[cfg(feature = "stats")]
+        crate::stats::record_conversion(true);
+
+        unsafe { Rc::from_raw_in(slice, alloc) }
     }
 }
@@ -0,0 +1,375 @@
+#[cfg(doc)]
+use alloc::{rc::Rc, sync::Arc};
+#[cfg(doc)]
+use super::{ArcVec, RcVec};
+
+use core::{
+    fmt::{self, Debug},
+    hash::{self, Hash},
+    mem::{self, MaybeUninit},
+    ops::{Deref, DerefMut},
+    slice,
+};
+
+use crate::{is_zst::IsZst as _, kind, RcKind};
+
+use super::SharedVec;
+
+/// Copy-on-write shared [`SharedVec`], `clone` only increases the reference count
+///
+/// The buffer of the [`SharedVec`] (including spare capacity) is shared as
+/// `Rc<[MaybeUninit<T>]>` with the initialized length,
+/// so converting from and into an unique [`SharedVec`] does not allocate
+///
+/// Generic over the [`RcKind`], usually used through [`CowRcVec`] or [`CowArcVec`]
+///
+/// # Examples
+///
+/// ```
+/// # use rc_vec::{rc_vec, CowRcVec};
+/// let mut a = CowRcVec::from(rc_vec![1, 2, 3]);
+/// let b = a.clone();
+/// assert_eq!(a.as_ptr(), b.as_ptr());
+///
+/// a.make_mut().push(4);
+/// assert_eq!(a, [1, 2, 3, 4]);
+/// assert_eq!(b, [1, 2, 3]);
+/// assert_ne!(a.as_ptr(), b.as_ptr());
+/// ```
+pub struct CowVec<T, K: RcKind> {
+    /// `None` only if never allocated, then `len` is zero
+    ///
+    /// `Weak` of the buffer never exists, the elements are dropped
+    /// by the last owner, found by the strong count in the header of the buffer,
+    /// even if the owners of [`Arc`] are dropped concurrently
+    buf: Option<K::Rc<[MaybeUninit<T>]>>,
+    len: usize,
+}
+
+/// [`CowVec`] based on [`Rc`]
+pub type CowRcVec<T> = CowVec<T, kind::Rc>;
+
+/// [`CowVec`] based on [`Arc`]
+pub type CowArcVec<T> = CowVec<T, kind::Arc>;
+
+/// Frozen [`RcVec`] created by [`RcVec::freeze`],
+/// shares the buffer with the spare capacity and the initialized length
+pub type FrozenRcVec<T> = CowRcVec<T>;
+
+/// Frozen [`ArcVec`] created by [`ArcVec::freeze`],
+/// shares the buffer with the spare capacity and the initialized length
+pub type FrozenArcVec<T> = CowArcVec<T>;
+
+impl<T, K: RcKind> Clone for CowVec<T, K> {
+    fn clone(&self) -> Self {
+        Self { buf: self.buf.clone(), len: self.len }
+    }
+}
+
+impl<T, K: RcKind> Drop for CowVec<T, K> {
+    fn drop(&mut self) {
+        let Some(buf) = self.buf.take() else { return };
+
+        // atomic for `Arc`, only the last of the concurrent owners drops the elements
+        if let Some(uniq) = unsafe { K::rc_slice_into_uniq(buf) } {
+            drop(unsafe { SharedVec::<T, K>::from_raw_uniq_slice(uniq, self.len) });
+        }
+    }
+}
+
+impl<T, K: RcKind> Default for CowVec<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, K: RcKind> Deref for CowVec<T, K> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        let Some(buf) = &self.buf else { return &[] };
+        unsafe { slice::from_raw_parts(K::rc_as_ptr(buf).cast::<T>(), self.len) }
+    }
+}
+
+impl<T, K: RcKind> CowVec<T, K> {
+    /// Create a new empty [`CowVec`], without allocation
+    pub const fn new() -> Self {
+        Self { buf: None, len: 0 }
+    }
+
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.as_slice().as_ptr()
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get allocated capacity of the shared buffer
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        match &self.buf {
+            Some(_) if T::ZST => usize::MAX,
+            Some(buf) => buf.len(),
+            None => 0,
+        }
+    }
+
+    /// Returns `true` if there are no other [`CowVec`] share the buffer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::{rc_vec, CowRcVec};
+    /// let a = CowRcVec::from(rc_vec![1, 2, 3]);
+    /// assert!(a.is_unique());
+    ///
+    /// let b = a.clone();
+    /// assert!(! a.is_unique());
+    /// drop(b);
+    /// assert!(a.is_unique());
+    /// ```
+    pub fn is_unique(&self) -> bool {
+        // never create `Weak` of the buffer
+        self.buf.as_ref().is_none_or(|buf| K::rc_strong_count(buf) == 1)
+    }
+
+    /// Convert into [`SharedVec`] without copy
+    ///
+    /// # Errors
+    /// - The buffer is shared
+    pub fn try_into_rc_vec(mut self) -> Result<SharedVec<T, K>, Self> {
+        let Some(buf) = self.buf.take() else {
+            return Ok(SharedVec::new());
+        };
+        let len = mem::take(&mut self.len);
+
+        match K::uniq_try_new(buf) {
+            Ok(uniq) => {
+                #[cfg(feature = "stats")]
+                crate::stats::record_conversion(true);
+
+                Ok(unsafe { SharedVec::from_raw_uniq_slice(uniq, len) })
+            },
+            Err(buf) => Err(Self { buf: Some(buf), len }),
+        }
+    }
+
+    /// Convert into [`SharedVec`], clone the elements only when the buffer is shared
+    pub fn into_rc_vec(self) -> SharedVec<T, K>
+    where T: Clone,
+    {
+        self.try_into_rc_vec().unwrap_or_else(|this| this.to_rc_vec())
+    }
+
+    /// Clone the elements into a new [`SharedVec`] with the same capacity
+    fn to_rc_vec(&self) -> SharedVec<T, K>
+    where T: Clone,
+    {
+        #[cfg(feature = "stats")]
+        crate::stats::record_conversion(false);
+
+        let mut vec = SharedVec::with_capacity(self.capacity());
+        vec.extend_from_slice(self);
+        vec
+    }
+
+    /// Thaw into [`SharedVec`] with the capacity intact,
+    /// clone the elements only when the buffer is shared,
+    /// same as [`into_rc_vec`](#method.into_rc_vec)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::RcVec;
+    /// let mut vec = RcVec::with_capacity(8);
+    /// vec.extend([1, 2, 3]);
+    ///
+    /// let frozen = vec.freeze();
+    /// let shared = frozen.clone();
+    /// assert_eq!(shared, [1, 2, 3]);
+    ///
+    /// // shared, clone the elements
+    /// let mut copy = shared.thaw();
+    /// copy.push(4);
+    /// assert_eq!(copy, [1, 2, 3, 4]);
+    ///
+    /// // sole owner, reuse the buffer
+    /// let vec = frozen.thaw();
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// assert_eq!(vec.capacity(), 8);
+    /// ```
+    #[inline]
+    pub fn thaw(self) -> SharedVec<T, K>
+    where T: Clone,
+    {
+        self.into_rc_vec()
+    }
+
+    /// Like [`Rc::make_mut`], get a mutable [`SharedVec`]
+    ///
+    /// If the buffer is shared, clone the elements into a new buffer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::{rc_vec, CowRcVec};
+    /// let mut a = CowRcVec::from(rc_vec![1, 2, 3]);
+    /// let ptr = a.as_ptr();
+    ///
+    /// // unique, no copy
+    /// a.make_mut()[0] = 4;
+    /// assert_eq!(a, [4, 2, 3]);
+    /// assert_eq!(a.as_ptr(), ptr);
+    /// ```
+    pub fn make_mut(&mut self) -> CowVecMut<'_, T, K>
+    where T: Clone,
+    {
+        let vec = match mem::take(self).try_into_rc_vec() {
+            Ok(vec) => vec,
+            Err(this) => this.to_rc_vec(),
+        };
+        CowVecMut { cow: self, vec }
+    }
+}
+
+impl<T, K: RcKind> SharedVec<T, K> {
+    /// Convert into [`CowVec`] without copy, same as [`freeze`](#method.freeze)
+    #[inline]
+    pub fn into_shared(self) -> CowVec<T, K> {
+        self.into()
+    }
+
+    /// Freeze into [`CowVec`] without copy and allocation,
+    /// unlike [`into_rc_slice`](#method.into_rc_slice), the spare capacity is kept
+    /// for the next [`thaw`](CowVec::thaw)
+    ///
+    /// Except a non-empty vector of ZST, which has no buffer,
+    /// then only the reference counts are allocated
+    #[inline]
+    pub fn freeze(self) -> CowVec<T, K> {
+        self.into()
+    }
+}
+
+impl<T, K: RcKind> From<SharedVec<T, K>> for CowVec<T, K> {
+    fn from(value: SharedVec<T, K>) -> Self {
+        let len = value.len();
+
+        // the elements of ZST need the reference counts of an allocation
+        let buf = if len == 0 {
+            value.into_raw_uniq_slice_optional()
+        } else {
+            Some(value.into_raw_uniq_slice())
+        };
+        Self { buf: buf.map(K::uniq_into_rc), len }
+    }
+}
+
+impl<T: Clone, K: RcKind> From<CowVec<T, K>> for SharedVec<T, K> {
+    fn from(value: CowVec<T, K>) -> Self {
+        value.into_rc_vec()
+    }
+}
+
+/// Mutable guard returned by [`CowVec::make_mut`],
+/// the unique [`SharedVec`] is shared again when the guard is dropped
+pub struct CowVecMut<'a, T, K: RcKind> {
+    cow: &'a mut CowVec<T, K>,
+    vec: SharedVec<T, K>,
+}
+
+impl<T, K: RcKind> Drop for CowVecMut<'_, T, K> {
+    fn drop(&mut self) {
+        *self.cow = mem::take(&mut self.vec).into();
+    }
+}
+
+/// [`CowVecMut`] based on [`Rc`]
+pub type CowRcVecMut<'a, T> = CowVecMut<'a, T, kind::Rc>;
+
+/// [`CowVecMut`] based on [`Arc`]
+pub type CowArcVecMut<'a, T> = CowVecMut<'a, T, kind::Arc>;
+
+impl<T, K: RcKind> Deref for CowVecMut<'_, T, K> {
+    type Target = SharedVec<T, K>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.vec
+    }
+}
+
+impl<T, K: RcKind> DerefMut for CowVecMut<'_, T, K> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.vec
+    }
+}
+
+impl<T: Debug, K: RcKind> Debug for CowVecMut<'_, T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.vec.fmt(f)
+    }
+}
+
+impl<T: Debug, K: RcKind> Debug for CowVec<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: Hash, K: RcKind> Hash for CowVec<T, K> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+impl<T, K: RcKind> AsRef<[T]> for CowVec<T, K> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: Eq, K: RcKind> Eq for CowVec<T, K> {}
+
+impl<T: PartialEq, K: RcKind> PartialEq for CowVec<T, K> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: PartialEq, K: RcKind> PartialEq<[T]> for CowVec<T, K> {
+    fn eq(&self, other: &[T]) -> bool {
+        **self == *other
+    }
+}
+
+impl<T: PartialEq, K: RcKind, const N: usize> PartialEq<[T; N]> for CowVec<T, K> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        **self == *other
+    }
+}
+
+impl<T: PartialEq, K: RcKind> PartialEq<&[T]> for CowVec<T, K> {
+    fn eq(&self, other: &&[T]) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: PartialEq, K: RcKind> PartialEq<SharedVec<T, K>> for CowVec<T, K> {
+    fn eq(&self, other: &SharedVec<T, K>) -> bool {
+        **self == **other
+    }
+}
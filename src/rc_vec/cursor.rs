@@ -0,0 +1,277 @@
+#[cfg(doc)]
+use alloc::{rc::Rc, sync::Arc};
+
+use core::fmt::{self, Debug};
+
+use crate::{
+    allocator::{Allocator, Global},
+    kind, RcKind,
+};
+
+use super::SharedVec;
+
+/// Like `std::io::Cursor` over an owned [`SharedVec<u8, K>`],
+/// writes overwrite the existing bytes and extend the vector
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "std")] {
+/// # use rc_vec::{RcVec, RcVecCursor};
+/// use std::io::{Read, Seek, SeekFrom, Write};
+///
+/// let mut cursor = RcVecCursor::new(RcVec::from(b"hello world".as_slice()));
+/// cursor.write_all(b"HELLO").unwrap();
+///
+/// cursor.seek(SeekFrom::End(-5)).unwrap();
+/// let mut word = String::new();
+/// cursor.read_to_string(&mut word).unwrap();
+/// assert_eq!(word, "world");
+///
+/// let rc = cursor.into_rc_slice();
+/// assert_eq!(*rc, *b"HELLO world");
+/// # }
+/// ```
+pub struct SharedVecCursor<K: RcKind, A: Allocator = Global> {
+    inner: SharedVec<u8, K, A>,
+    pos: u64,
+}
+
+/// [`SharedVecCursor`] based on [`Rc`]
+pub type RcVecCursor<A = Global> = SharedVecCursor<kind::Rc, A>;
+
+/// [`SharedVecCursor`] based on [`Arc`]
+pub type ArcVecCursor<A = Global> = SharedVecCursor<kind::Arc, A>;
+
+impl<K: RcKind, A: Allocator> Debug for SharedVecCursor<K, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedVecCursor")
+            .field("inner", &self.inner.as_slice())
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
+
+impl<K: RcKind, A: Allocator + Clone> Clone for SharedVecCursor<K, A> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), pos: self.pos }
+    }
+}
+
+impl<K: RcKind> Default for SharedVecCursor<K> {
+    fn default() -> Self {
+        Self::new(SharedVec::new())
+    }
+}
+
+impl<K: RcKind, A: Allocator> From<SharedVec<u8, K, A>> for SharedVecCursor<K, A> {
+    fn from(value: SharedVec<u8, K, A>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<K: RcKind, A: Allocator> SharedVecCursor<K, A> {
+    /// Create a cursor at the start of `inner`
+    pub const fn new(inner: SharedVec<u8, K, A>) -> Self {
+        Self { inner, pos: 0 }
+    }
+
+    /// Get back the vector
+    pub fn into_inner(self) -> SharedVec<u8, K, A> {
+        self.inner
+    }
+
+    pub const fn get_ref(&self) -> &SharedVec<u8, K, A> {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut SharedVec<u8, K, A> {
+        &mut self.inner
+    }
+
+    pub const fn position(&self) -> u64 {
+        self.pos
+    }
+
+    pub fn set_position(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+
+    /// The bytes after the position, empty if the position is past the end
+    pub fn remaining_slice(&self) -> &[u8] {
+        let start = self.pos.min(self.inner.len() as u64) as usize;
+        &self.inner[start..]
+    }
+
+    /// Returns `true` if the position is at or past the end
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.inner.len() as u64
+    }
+
+    /// Advance the position, like [`BufRead::consume`](std::io::BufRead::consume)
+    #[cfg(any(feature = "std", feature = "embedded_io"))]
+    fn consume(&mut self, amt: usize) {
+        self.pos = self.pos.saturating_add(amt as u64);
+    }
+
+    /// Write `buf` at the position, pad zeros if the position is past the end
+    ///
+    /// Returns `None` if the position cannot fit in `usize`
+    #[cfg(any(feature = "std", feature = "embedded_io"))]
+    fn write_at_pos(&mut self, buf: &[u8]) -> Option<usize> {
+        let pos = usize::try_from(self.pos).ok()?;
+        let end = pos.checked_add(buf.len())?;
+
+        if pos > self.inner.len() {
+            self.inner.resize(pos, 0);
+        }
+
+        let overlap = (self.inner.len() - pos).min(buf.len());
+        let (overwrite, extend) = buf.split_at(overlap);
+
+        self.inner[pos..pos+overlap].copy_from_slice(overwrite);
+        self.inner.extend_from_copy_slice(extend);
+        self.pos = end as u64;
+
+        Some(buf.len())
+    }
+}
+
+impl<K: RcKind> SharedVecCursor<K> {
+    /// Freeze the vector into `Rc<[u8]>` or `Arc<[u8]>`, see [`SharedVec::into_rc_slice`]
+    pub fn into_rc_slice(self) -> K::Rc<[u8]> {
+        self.inner.into_rc_slice()
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+mod io_impls {
+    use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+
+    use super::*;
+
+    impl<K: RcKind, A: Allocator> Read for SharedVecCursor<K, A> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = Read::read(&mut self.remaining_slice(), buf)?;
+            self.consume(n);
+            Ok(n)
+        }
+
+        fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+            Read::read_exact(&mut self.remaining_slice(), buf)?;
+            self.consume(buf.len());
+            Ok(())
+        }
+    }
+
+    impl<K: RcKind, A: Allocator> BufRead for SharedVecCursor<K, A> {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            Ok(self.remaining_slice())
+        }
+
+        fn consume(&mut self, amt: usize) {
+            SharedVecCursor::consume(self, amt);
+        }
+    }
+
+    impl<K: RcKind, A: Allocator> Seek for SharedVecCursor<K, A> {
+        fn seek(&mut self, style: SeekFrom) -> io::Result<u64> {
+            let (base, offset) = match style {
+                SeekFrom::Start(n) => {
+                    self.pos = n;
+                    return Ok(n);
+                },
+                SeekFrom::End(n) => (self.inner.len() as u64, n),
+                SeekFrom::Current(n) => (self.pos, n),
+            };
+
+            match base.checked_add_signed(offset) {
+                Some(n) => {
+                    self.pos = n;
+                    Ok(n)
+                },
+                None => Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                )),
+            }
+        }
+
+        fn stream_position(&mut self) -> io::Result<u64> {
+            Ok(self.pos)
+        }
+    }
+
+    impl<K: RcKind, A: Allocator> Write for SharedVecCursor<K, A> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.write_at_pos(buf).ok_or_else(|| io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "cursor position exceeds maximum possible vector length",
+            ))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "embedded_io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded_io")))]
+mod embedded_io_impls {
+    use embedded_io::{BufRead, ErrorKind, ErrorType, Read, Seek, SeekFrom, Write};
+
+    use super::*;
+
+    impl<K: RcKind, A: Allocator> ErrorType for SharedVecCursor<K, A> {
+        type Error = ErrorKind;
+    }
+
+    impl<K: RcKind, A: Allocator> Read for SharedVecCursor<K, A> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+            let remaining = self.remaining_slice();
+            let n = buf.len().min(remaining.len());
+            buf[..n].copy_from_slice(&remaining[..n]);
+            self.consume(n);
+            Ok(n)
+        }
+    }
+
+    impl<K: RcKind, A: Allocator> BufRead for SharedVecCursor<K, A> {
+        fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+            Ok(self.remaining_slice())
+        }
+
+        fn consume(&mut self, amt: usize) {
+            SharedVecCursor::consume(self, amt);
+        }
+    }
+
+    impl<K: RcKind, A: Allocator> Seek for SharedVecCursor<K, A> {
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Error> {
+            let (base, offset) = match pos {
+                SeekFrom::Start(n) => {
+                    self.pos = n;
+                    return Ok(n);
+                },
+                SeekFrom::End(n) => (self.inner.len() as u64, n),
+                SeekFrom::Current(n) => (self.pos, n),
+            };
+
+            let n = base.checked_add_signed(offset).ok_or(ErrorKind::InvalidInput)?;
+            self.pos = n;
+            Ok(n)
+        }
+    }
+
+    impl<K: RcKind, A: Allocator> Write for SharedVecCursor<K, A> {
+        fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+            self.write_at_pos(buf).ok_or(ErrorKind::OutOfMemory)
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+}
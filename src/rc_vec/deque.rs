@@ -0,0 +1,522 @@
+#[cfg(doc)]
+use alloc::{collections::VecDeque, rc::Rc, sync::Arc};
+
+use core::{
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    iter::Chain,
+    mem::ManuallyDrop,
+    ops::{Index, IndexMut},
+    ptr, slice,
+};
+
+use crate::{
+    allocator::{Allocator, Global},
+    is_zst::IsZst as _,
+    kind,
+    raw::RawVec,
+    RcKind, TryReserveError,
+};
+
+use super::{SharedVec, SharedVecIntoIter};
+
+/// Like [`VecDeque`], a ring buffer can be frozen into [`Rc<[T]>`](Rc)
+/// without copy to a new allocation
+///
+/// Generic over the [`RcKind`], usually used through [`RcVecDeque`] or [`ArcVecDeque`]
+///
+/// # Examples
+///
+/// ```
+/// # use std::rc::Rc;
+/// use rc_vec::RcVecDeque;
+///
+/// let mut deque = RcVecDeque::new();
+/// deque.push_back(2);
+/// deque.push_back(3);
+/// deque.push_front(1);
+/// assert_eq!(deque.pop_back(), Some(3));
+/// deque.push_front(0);
+///
+/// let rc: Rc<[i32]> = deque.into_rc_slice();
+/// assert_eq!(*rc, [0, 1, 2]);
+/// ```
+pub struct SharedVecDeque<T, K: RcKind, A: Allocator = Global> {
+    raw: RawVec<T, K, A>,
+    /// Physical index of the first element
+    head: usize,
+    len: usize,
+}
+
+/// [`SharedVecDeque`] based on [`Rc`]
+pub type RcVecDeque<T, A = Global> = SharedVecDeque<T, kind::Rc, A>;
+
+/// [`SharedVecDeque`] based on [`Arc`]
+pub type ArcVecDeque<T, A = Global> = SharedVecDeque<T, kind::Arc, A>;
+
+// The buffer is uniquely owned, like `VecDeque<T>`
+unsafe impl<T: Send, A: Allocator + Send> Send for ArcVecDeque<T, A> { }
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for ArcVecDeque<T, A> { }
+
+impl<T, K: RcKind, A: Allocator> Drop for SharedVecDeque<T, K, A> {
+    fn drop(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        unsafe {
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+}
+
+impl<T, K: RcKind> Default for SharedVecDeque<T, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, K: RcKind> SharedVecDeque<T, K> {
+    /// Create a new empty deque
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    /// Create a new empty deque with at least capacity of `capacity`
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, Global)
+    }
+
+    /// Rotate the elements to the start of the buffer,
+    /// and freeze it into [`Rc<[T]>`](Rc) or [`Arc<[T]>`](Arc),
+    /// see [`SharedVec::into_rc_slice`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::RcVecDeque;
+    /// let mut deque = RcVecDeque::with_capacity(4);
+    /// deque.extend([3, 4, 5]);
+    /// deque.pop_front();
+    /// deque.push_back(6);
+    /// deque.push_back(7);
+    /// assert_eq!(deque.as_slices(), (&[4, 5, 6][..], &[7][..]));
+    ///
+    /// let rc = deque.into_rc_slice();
+    /// assert_eq!(*rc, [4, 5, 6, 7]);
+    /// ```
+    pub fn into_rc_slice(self) -> K::Rc<[T]> {
+        SharedVec::from(self).into_rc_slice()
+    }
+}
+
+impl<T, K: RcKind, A: Allocator> SharedVecDeque<T, K, A> {
+    /// Like [`new`](#method.new), but with the allocator `alloc`
+    pub const fn new_in(alloc: A) -> Self {
+        Self { raw: RawVec::new_in(alloc), head: 0, len: 0 }
+    }
+
+    /// Like [`with_capacity`](#method.with_capacity), but with the allocator `alloc`
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self { raw: RawVec::with_capacity_in(capacity, alloc), head: 0, len: 0 }
+    }
+
+    /// Returns a reference to the underlying allocator
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.raw.allocator()
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.raw.capacity()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    fn ptr(&self) -> *mut T {
+        self.raw.as_ptr().cast_mut()
+    }
+
+    /// Physical index of `idx` after `head` in the ring
+    #[inline]
+    fn wrap_add(&self, idx: usize, addend: usize) -> usize {
+        wrap_index(idx.wrapping_add(addend), self.capacity())
+    }
+
+    #[inline]
+    fn wrap_sub(&self, idx: usize, subtrahend: usize) -> usize {
+        wrap_index(
+            idx.wrapping_sub(subtrahend).wrapping_add(self.capacity()),
+            self.capacity(),
+        )
+    }
+
+    #[inline]
+    fn to_physical_idx(&self, idx: usize) -> usize {
+        self.wrap_add(self.head, idx)
+    }
+
+    #[inline]
+    fn is_contiguous(&self) -> bool {
+        self.head <= self.capacity() - self.len
+    }
+
+    /// Physical ranges of the front and back part
+    fn slice_ranges(&self) -> ((usize, usize), (usize, usize)) {
+        if T::ZST || self.is_contiguous() {
+            ((self.head, self.len), (0, 0))
+        } else {
+            let front_len = self.capacity() - self.head;
+            ((self.head, front_len), (0, self.len - front_len))
+        }
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the deque
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let ((front, front_len), (back, back_len)) = self.slice_ranges();
+        let ptr = self.ptr();
+        unsafe {(
+            slice::from_raw_parts(ptr.add(front), front_len),
+            slice::from_raw_parts(ptr.add(back), back_len),
+        )}
+    }
+
+    /// Like [`as_slices`](#method.as_slices), but mutable
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let ((front, front_len), (back, back_len)) = self.slice_ranges();
+        let ptr = self.ptr();
+        unsafe {(
+            slice::from_raw_parts_mut(ptr.add(front), front_len),
+            slice::from_raw_parts_mut(ptr.add(back), back_len),
+        )}
+    }
+
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let (front, back) = self.as_slices();
+        front.iter().chain(back)
+    }
+
+    pub fn iter_mut(&mut self) -> Chain<slice::IterMut<'_, T>, slice::IterMut<'_, T>> {
+        let (front, back) = self.as_mut_slices();
+        front.iter_mut().chain(back)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len {
+            let idx = self.to_physical_idx(index);
+            unsafe { Some(&*self.ptr().add(idx)) }
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len {
+            let idx = self.to_physical_idx(index);
+            unsafe { Some(&mut *self.ptr().add(idx)) }
+        } else {
+            None
+        }
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.wrapping_sub(1))
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len.wrapping_sub(1))
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        let old_cap = self.capacity();
+        self.raw.reserve(self.len, additional);
+        unsafe { self.handle_capacity_increase(old_cap) }
+    }
+
+    pub fn reserve_exact(&mut self, additional: usize) {
+        let old_cap = self.capacity();
+        self.raw.reserve_exact(self.len, additional);
+        unsafe { self.handle_capacity_increase(old_cap) }
+    }
+
+    /// # Errors
+    /// - The capacity exceeds `isize::MAX` bytes
+    /// - The allocator returned an error
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let old_cap = self.capacity();
+        self.raw.try_reserve(self.len, additional)?;
+        unsafe { self.handle_capacity_increase(old_cap) }
+        Ok(())
+    }
+
+    fn grow(&mut self) {
+        debug_assert_eq!(self.len, self.capacity());
+        let old_cap = self.capacity();
+        self.raw.reserve_for_push(self.len);
+        unsafe { self.handle_capacity_increase(old_cap) }
+    }
+
+    /// Move the wrapped part after the grown buffer, keep the ring valid
+    ///
+    /// # Safety
+    /// - `old_cap` is the capacity before the buffer grown
+    unsafe fn handle_capacity_increase(&mut self, old_cap: usize) {
+        let new_cap = self.capacity();
+        debug_assert!(new_cap >= old_cap);
+
+        if self.head <= old_cap - self.len {
+            // [o o o o o . . .]
+            //  H
+            return;
+        }
+
+        let head_len = old_cap - self.head;
+        let tail_len = self.len - head_len;
+        let ptr = self.ptr();
+
+        unsafe {
+            if tail_len < head_len && tail_len <= new_cap - old_cap {
+                // [o o . o o o o o . . .] -> [. . . o o o o o o o .]
+                //        H                          H
+                ptr::copy_nonoverlapping(ptr, ptr.add(old_cap), tail_len);
+            } else {
+                // [o o o o . o o . . .] -> [o o o o . . . . o o]
+                //            H                             H
+                let new_head = new_cap - head_len;
+                ptr::copy(ptr.add(self.head), ptr.add(new_head), head_len);
+                self.head = new_head;
+            }
+        }
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        if self.len == self.capacity() {
+            self.grow();
+        }
+
+        let idx = self.to_physical_idx(self.len);
+        unsafe { self.ptr().add(idx).write(value) }
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        if self.len == self.capacity() {
+            self.grow();
+        }
+
+        self.head = self.wrap_sub(self.head, 1);
+        unsafe { self.ptr().add(self.head).write(value) }
+        self.len += 1;
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        let idx = self.to_physical_idx(self.len);
+        unsafe { Some(self.ptr().add(idx).read()) }
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let old_head = self.head;
+        self.head = self.to_physical_idx(1);
+        self.len -= 1;
+        unsafe { Some(self.ptr().add(old_head).read()) }
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            drop(self.pop_back());
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+        self.head = 0;
+    }
+
+    /// Rotate the elements to be contiguous in the buffer,
+    /// returns the slice of all elements
+    ///
+    /// Wrapped elements are rotated in place, without a new allocation
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::RcVecDeque;
+    /// let mut deque = RcVecDeque::with_capacity(3);
+    /// deque.push_back(2);
+    /// deque.push_back(3);
+    /// deque.push_front(1);
+    /// assert_eq!(deque.as_slices(), (&[1][..], &[2, 3][..]));
+    ///
+    /// assert_eq!(deque.make_contiguous(), [1, 2, 3]);
+    /// assert_eq!(deque.as_slices(), (&[1, 2, 3][..], &[][..]));
+    /// ```
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if T::ZST {
+            self.head = 0;
+        } else if !self.is_contiguous() {
+            // Uninitialized slots are moved with the elements,
+            // physical index `head` becomes `0`
+            self.raw.slice_mut().rotate_left(self.head);
+            self.head = 0;
+        }
+
+        let (front, back) = self.as_mut_slices();
+        debug_assert!(back.is_empty());
+        front
+    }
+
+    /// Move the elements to the start of the buffer
+    fn move_to_start(&mut self) {
+        self.make_contiguous();
+
+        if self.head != 0 {
+            let ptr = self.ptr();
+            unsafe { ptr::copy(ptr.add(self.head), ptr, self.len) }
+            self.head = 0;
+        }
+    }
+}
+
+#[inline]
+fn wrap_index(logical_index: usize, capacity: usize) -> usize {
+    debug_assert!(
+        (logical_index == 0 && capacity == 0)
+            || logical_index < capacity
+            || (logical_index - capacity) < capacity
+    );
+    if logical_index >= capacity {
+        logical_index - capacity
+    } else {
+        logical_index
+    }
+}
+
+impl<T, K: RcKind, A: Allocator> From<SharedVec<T, K, A>> for SharedVecDeque<T, K, A> {
+    /// Reuse the buffer of `vec` without copy
+    fn from(vec: SharedVec<T, K, A>) -> Self {
+        let len = vec.len();
+        Self { raw: vec.into_raw_vec(), head: 0, len }
+    }
+}
+
+impl<T, K: RcKind, A: Allocator> From<SharedVecDeque<T, K, A>> for SharedVec<T, K, A> {
+    /// Reuse the buffer of `deque`, the elements are moved to the start in place
+    fn from(mut deque: SharedVecDeque<T, K, A>) -> Self {
+        deque.move_to_start();
+
+        let deque = ManuallyDrop::new(deque);
+        let raw = unsafe { ptr::read(&deque.raw) };
+        SharedVec { raw, len: deque.len }
+    }
+}
+
+impl<T: Clone, K: RcKind, A: Allocator + Clone> Clone for SharedVecDeque<T, K, A> {
+    fn clone(&self) -> Self {
+        let mut deque = Self::with_capacity_in(self.len, self.allocator().clone());
+        deque.extend(self.iter().cloned());
+        deque
+    }
+}
+
+impl<T: Debug, K: RcKind, A: Allocator> Debug for SharedVecDeque<T, K, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, K: RcKind, A: Allocator> PartialEq for SharedVecDeque<T, K, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, K: RcKind, A: Allocator> Eq for SharedVecDeque<T, K, A> {}
+
+impl<T: Hash, K: RcKind, A: Allocator> Hash for SharedVecDeque<T, K, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        self.iter().for_each(|elem| elem.hash(state));
+    }
+}
+
+impl<T, K: RcKind, A: Allocator> Index<usize> for SharedVecDeque<T, K, A> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("out of bounds access")
+    }
+}
+
+impl<T, K: RcKind, A: Allocator> IndexMut<usize> for SharedVecDeque<T, K, A> {
+    #[track_caller]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("out of bounds access")
+    }
+}
+
+impl<T, K: RcKind, A: Allocator> Extend<T> for SharedVecDeque<T, K, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        iter.for_each(|elem| self.push_back(elem));
+    }
+}
+
+impl<T, K: RcKind> FromIterator<T> for SharedVecDeque<T, K> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        SharedVec::from_iter(iter).into()
+    }
+}
+
+impl<T, K: RcKind, A: Allocator> IntoIterator for SharedVecDeque<T, K, A> {
+    type Item = T;
+    type IntoIter = SharedVecIntoIter<T, K, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        SharedVec::from(self).into_iter()
+    }
+}
+
+impl<'a, T, K: RcKind, A: Allocator> IntoIterator for &'a SharedVecDeque<T, K, A> {
+    type Item = &'a T;
+    type IntoIter = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, K: RcKind, A: Allocator> IntoIterator for &'a mut SharedVecDeque<T, K, A> {
+    type Item = &'a mut T;
+    type IntoIter = Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
@@ -5,44 +5,48 @@ use core::{
     ptr::{self, NonNull},
     slice,
 };
-use crate::is_zst::IsZst;
-use rc_vec_proc_macro::rc_impl_gen_arc_impl;
+use crate::{
+    allocator::{Allocator, Global},
+    growth::{Doubling, GrowthPolicy},
+    is_zst::IsZst,
+    kind, RcKind,
+};
 
-use super::{ArcVec, RcVec};
+use super::SharedVec;
 
-#[rc_impl_gen_arc_impl]
-pub struct RcVecDrain<'a, T: 'a> {
+/// Like [`alloc::vec::Drain`], created by [`SharedVec::drain`]
+pub struct SharedVecDrain<'a, T: 'a, K: RcKind, A: Allocator = Global, G: GrowthPolicy = Doubling> {
     pub(super) tail_start: usize,
     pub(super) tail_len: usize,
     pub(super) iter: slice::Iter<'a, T>,
-    pub(super) vec: NonNull<RcVec<T>>,
+    pub(super) vec: NonNull<SharedVec<T, K, A, G>>,
 }
 
-impl<'a, T: Debug + 'a> Debug for RcVecDrain<'a, T> {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_tuple("RcVecDrain")
-            .field(&self.iter.as_slice())
-            .finish()
-    }
-}
+/// [`SharedVecDrain`] of [`RcVec`](crate::RcVec)
+pub type RcVecDrain<'a, T, A = Global, G = Doubling> = SharedVecDrain<'a, T, kind::Rc, A, G>;
 
-impl<'a, T: Debug + 'a> Debug for ArcVecDrain<'a, T> {
+/// [`SharedVecDrain`] of [`ArcVec`](crate::ArcVec)
+pub type ArcVecDrain<'a, T, A = Global, G = Doubling> = SharedVecDrain<'a, T, kind::Arc, A, G>;
+
+impl<'a, T: Debug + 'a, K: RcKind, A: Allocator, G: GrowthPolicy> Debug for SharedVecDrain<'a, T, K, A, G> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_tuple("ArcVecDrain")
+        f.debug_tuple("SharedVecDrain")
             .field(&self.iter.as_slice())
             .finish()
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<'a, T: 'a> RcVecDrain<'a, T> {
+// Like `&mut ArcVec<T, A>`
+unsafe impl<T: Send, A: Allocator + Send, G: GrowthPolicy> Send for ArcVecDrain<'_, T, A, G> { }
+unsafe impl<T: Sync, A: Allocator + Sync, G: GrowthPolicy> Sync for ArcVecDrain<'_, T, A, G> { }
+
+impl<'a, T: 'a, K: RcKind, A: Allocator, G: GrowthPolicy> SharedVecDrain<'a, T, K, A, G> {
     pub fn as_slice(&self) -> &[T] {
         self.iter.as_slice()
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<'a, T: 'a> Iterator for RcVecDrain<'a, T> {
+impl<'a, T: 'a, K: RcKind, A: Allocator, G: GrowthPolicy> Iterator for SharedVecDrain<'a, T, K, A, G> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -54,18 +58,15 @@ impl<'a, T: 'a> Iterator for RcVecDrain<'a, T> {
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<'a, T: 'a> DoubleEndedIterator for RcVecDrain<'a, T> {
+impl<'a, T: 'a, K: RcKind, A: Allocator, G: GrowthPolicy> DoubleEndedIterator for SharedVecDrain<'a, T, K, A, G> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.iter.next_back().map(|ele| unsafe { ptr::read(ele) })
     }
 }
 
-#[rc_impl_gen_arc_impl]
-struct RcVecMoveGuard<'r, 'a, T>(&'r mut RcVecDrain<'a, T>);
+struct MoveGuard<'r, 'a, T, K: RcKind, A: Allocator, G: GrowthPolicy>(&'r mut SharedVecDrain<'a, T, K, A, G>);
 
-#[rc_impl_gen_arc_impl]
-impl<'r, 'a, T> Drop for RcVecMoveGuard<'r, 'a, T> {
+impl<'r, 'a, T, K: RcKind, A: Allocator, G: GrowthPolicy> Drop for MoveGuard<'r, 'a, T, K, A, G> {
     fn drop(&mut self) {
         unsafe {
             let src_vec = self.0.vec.as_mut();
@@ -83,8 +84,7 @@ impl<'r, 'a, T> Drop for RcVecMoveGuard<'r, 'a, T> {
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<'a, T: 'a> Drop for RcVecDrain<'a, T> {
+impl<'a, T: 'a, K: RcKind, A: Allocator, G: GrowthPolicy> Drop for SharedVecDrain<'a, T, K, A, G> {
     fn drop(&mut self) {
         let iter = take(&mut self.iter);
         let drop_len = iter.len();
@@ -103,7 +103,7 @@ impl<'a, T: 'a> Drop for RcVecDrain<'a, T> {
             return;
         }
 
-        let _guard = RcVecMoveGuard(self);
+        let _guard = MoveGuard(self);
 
         if drop_len == 0 {
             return;
@@ -120,8 +120,219 @@ impl<'a, T: 'a> Drop for RcVecDrain<'a, T> {
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> ExactSizeIterator for RcVecDrain<'_, T> { }
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> ExactSizeIterator for SharedVecDrain<'_, T, K, A, G> { }
+
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> FusedIterator for SharedVecDrain<'_, T, K, A, G> { }
+
+impl<'a, T: 'a, K: RcKind, A: Allocator, G: GrowthPolicy> SharedVecDrain<'a, T, K, A, G> {
+    /// The range from `self.vec.len` to `self.tail_start` contains elements
+    /// that have been moved out.
+    /// Fill that range as much as possible with new elements from the `replace_with` iterator.
+    /// Returns `true` if we filled the entire range. (`replace_with.next()` didn't return `None`.)
+    unsafe fn fill<I: Iterator<Item = T>>(&mut self, replace_with: &mut I) -> bool {
+        let vec = self.vec.as_mut();
+        let range_start = vec.len();
+        let range_end = self.tail_start;
+        let range_slice = slice::from_raw_parts_mut(
+            vec.as_mut_ptr().add(range_start),
+            range_end - range_start,
+        );
+
+        for place in range_slice {
+            if let Some(new_item) = replace_with.next() {
+                ptr::write(place, new_item);
+                vec.set_len(vec.len() + 1);
+            } else {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Makes room for inserting more elements before the tail.
+    unsafe fn move_tail(&mut self, additional: usize) {
+        let vec = self.vec.as_mut();
+        let len = self.tail_start + self.tail_len;
+        vec.raw.reserve(len, additional);
+
+        let new_tail_start = self.tail_start + additional;
+        let src = vec.as_ptr().add(self.tail_start);
+        let dst = vec.as_mut_ptr().add(new_tail_start);
+        ptr::copy(src, dst, self.tail_len);
+
+        self.tail_start = new_tail_start;
+    }
+}
+
+/// Like [`alloc::vec::Splice`], created by [`SharedVec::splice`]
+pub struct SharedVecSplice<'a, I: Iterator + 'a, K: RcKind, A: Allocator = Global, G: GrowthPolicy = Doubling> {
+    pub(super) drain: SharedVecDrain<'a, I::Item, K, A, G>,
+    pub(super) replace_with: I,
+}
+
+/// [`SharedVecSplice`] of [`RcVec`](crate::RcVec)
+pub type RcVecSplice<'a, I, A = Global, G = Doubling> = SharedVecSplice<'a, I, kind::Rc, A, G>;
+
+/// [`SharedVecSplice`] of [`ArcVec`](crate::ArcVec)
+pub type ArcVecSplice<'a, I, A = Global, G = Doubling> = SharedVecSplice<'a, I, kind::Arc, A, G>;
+
+impl<'a, I, K: RcKind, A: Allocator, G: GrowthPolicy> Debug for SharedVecSplice<'a, I, K, A, G>
+where I: Iterator + Debug + 'a,
+      I::Item: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("SharedVecSplice")
+            .field(&self.drain)
+            .field(&self.replace_with)
+            .finish()
+    }
+}
+
+impl<I: Iterator, K: RcKind, A: Allocator, G: GrowthPolicy> Iterator for SharedVecSplice<'_, I, K, A, G> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.drain.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl<I: Iterator, K: RcKind, A: Allocator, G: GrowthPolicy> DoubleEndedIterator for SharedVecSplice<'_, I, K, A, G> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.drain.next_back()
+    }
+}
+
+impl<I: Iterator, K: RcKind, A: Allocator, G: GrowthPolicy> ExactSizeIterator for SharedVecSplice<'_, I, K, A, G> { }
+
+impl<I: Iterator, K: RcKind, A: Allocator, G: GrowthPolicy> Drop for SharedVecSplice<'_, I, K, A, G> {
+    fn drop(&mut self) {
+        // This implement code from alloc::vec
+
+        self.drain.by_ref().for_each(drop);
+        // At this point draining is done and the only remaining tasks are splicing
+        // and moving things into the final place.
+        // Which means we can replace the slice::Iter with pointers that won't point to deallocated
+        // memory, so that Drain::drop is still allowed to call iter.len()
+        self.drain.iter = [].iter();
+
+        unsafe {
+            if self.drain.tail_len == 0 {
+                self.drain.vec.as_mut().extend(self.replace_with.by_ref());
+                return;
+            }
+
+            // First fill the range left by drain().
+            if !self.drain.fill(&mut self.replace_with) {
+                return;
+            }
+
+            // There may be more elements. Use the lower bound as an estimate.
+            let (lower_bound, _upper_bound) = self.replace_with.size_hint();
+            if lower_bound > 0 {
+                self.drain.move_tail(lower_bound);
+                if !self.drain.fill(&mut self.replace_with) {
+                    return;
+                }
+            }
+
+            // Collect any remaining elements.
+            // This is a zero-length vector which does not allocate if `lower_bound` was exact.
+            let mut collected = self.replace_with.by_ref()
+                .collect::<SharedVec<I::Item, K>>()
+                .into_iter();
+            // Now we have an exact count.
+            if collected.len() > 0 {
+                self.drain.move_tail(collected.len());
+                let filled = self.drain.fill(&mut collected);
+                debug_assert!(filled);
+                debug_assert_eq!(collected.len(), 0);
+            }
+        }
+        // Let `Drain::drop` move the tail back if necessary and restore `vec.len`.
+    }
+}
+
+/// Like [`alloc::vec::ExtractIf`], created by [`SharedVec::extract_if`]
+pub struct SharedVecExtractIf<'a, T, F, K: RcKind, A: Allocator = Global, G: GrowthPolicy = Doubling> {
+    pub(super) vec: &'a mut SharedVec<T, K, A, G>,
+    /// The index of the item that will be inspected by the next call to `next`.
+    pub(super) idx: usize,
+    /// Elements at and beyond this point will be retained.
+    pub(super) end: usize,
+    /// The number of items that have been drained (removed) thus far.
+    pub(super) del: usize,
+    /// The original length of `vec` prior to draining.
+    pub(super) old_len: usize,
+    /// The filter test predicate.
+    pub(super) pred: F,
+}
+
+/// [`SharedVecExtractIf`] of [`RcVec`](crate::RcVec)
+pub type RcVecExtractIf<'a, T, F, A = Global, G = Doubling> = SharedVecExtractIf<'a, T, F, kind::Rc, A, G>;
+
+/// [`SharedVecExtractIf`] of [`ArcVec`](crate::ArcVec)
+pub type ArcVecExtractIf<'a, T, F, A = Global, G = Doubling> = SharedVecExtractIf<'a, T, F, kind::Arc, A, G>;
 
-#[rc_impl_gen_arc_impl]
-impl<T> FusedIterator for RcVecDrain<'_, T> { }
+impl<T: Debug, F, K: RcKind, A: Allocator, G: GrowthPolicy> Debug for SharedVecExtractIf<'_, T, F, K, A, G> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let peek = unsafe {
+            slice::from_raw_parts(self.vec.as_ptr(), self.old_len)
+        }.get(self.idx..self.end);
+        f.debug_tuple("SharedVecExtractIf")
+            .field(&peek)
+            .finish()
+    }
+}
+
+impl<T, F, K: RcKind, A: Allocator, G: GrowthPolicy> Iterator for SharedVecExtractIf<'_, T, F, K, A, G>
+where F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        unsafe {
+            while self.idx < self.end {
+                let i = self.idx;
+                let v = slice::from_raw_parts_mut(self.vec.as_mut_ptr(), self.old_len);
+                let drained = (self.pred)(&mut v[i]);
+                // Update the index *after* the predicate is called. If the index
+                // is updated prior and the predicate panics, the element at this
+                // index would be leaked.
+                self.idx += 1;
+                if drained {
+                    self.del += 1;
+                    return Some(ptr::read(&v[i]));
+                } else if self.del > 0 {
+                    let del = self.del;
+                    let src: *const T = &v[i];
+                    let dst: *mut T = &mut v[i - del];
+                    ptr::copy_nonoverlapping(src, dst, 1);
+                }
+            }
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.idx))
+    }
+}
+
+impl<T, F, K: RcKind, A: Allocator, G: GrowthPolicy> Drop for SharedVecExtractIf<'_, T, F, K, A, G> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.idx < self.old_len && self.del > 0 {
+                let ptr = self.vec.as_mut_ptr();
+                let src = ptr.add(self.idx);
+                let dst = src.sub(self.del);
+                let tail_len = self.old_len - self.idx;
+                src.copy_to(dst, tail_len);
+            }
+            self.vec.set_len(self.old_len - self.del);
+        }
+    }
+}
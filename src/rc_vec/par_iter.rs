@@ -0,0 +1,251 @@
+//! Parallel iterators of [`ArcVec`], with the `rayon` feature
+
+use alloc::{collections::LinkedList, vec::Vec};
+use core::{
+    fmt::{self, Debug},
+    iter::FusedIterator,
+    mem,
+    ops::RangeBounds,
+    ptr, slice,
+};
+use rayon::iter::{
+    plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer},
+    FromParallelIterator, IndexedParallelIterator, IntoParallelIterator,
+    ParallelDrainRange, ParallelExtend, ParallelIterator,
+};
+
+use crate::allocator::{Allocator, Global};
+
+use super::{ArcVec, ArcVecDrain};
+
+/// Parallel iterator that moves out of an [`ArcVec`],
+/// created by [`IntoParallelIterator::into_par_iter`]
+pub struct ArcVecParIntoIter<T: Send, A: Allocator = Global> {
+    vec: ArcVec<T, A>,
+}
+
+impl<T: Send + Debug, A: Allocator> Debug for ArcVecParIntoIter<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArcVecParIntoIter")
+            .field(&self.vec.as_slice())
+            .finish()
+    }
+}
+
+impl<T: Send, A: Allocator + Send> IntoParallelIterator for ArcVec<T, A> {
+    type Item = T;
+    type Iter = ArcVecParIntoIter<T, A>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ArcVecParIntoIter { vec: self }
+    }
+}
+
+impl<T: Send, A: Allocator + Send> ParallelIterator for ArcVecParIntoIter<T, A> {
+    type Item = T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.vec.len())
+    }
+}
+
+impl<T: Send, A: Allocator + Send> IndexedParallelIterator for ArcVecParIntoIter<T, A> {
+    fn drive<C>(self, consumer: C) -> C::Result
+    where C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    fn with_producer<CB>(mut self, callback: CB) -> CB::Output
+    where CB: ProducerCallback<Self::Item>,
+    {
+        // Drain every element, and then the vector only needs to free its buffer
+        self.vec.par_drain(..).with_producer(callback)
+    }
+}
+
+/// Draining parallel iterator of [`ArcVec`] built on [`ArcVecDrain`],
+/// created by [`ParallelDrainRange::par_drain`]
+pub struct ArcVecParDrain<'a, T: Send, A: Allocator = Global> {
+    drain: ArcVecDrain<'a, T, A>,
+}
+
+impl<T: Send + Debug, A: Allocator> Debug for ArcVecParDrain<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArcVecParDrain")
+            .field(&self.drain.as_slice())
+            .finish()
+    }
+}
+
+impl<'a, T: Send, A: Allocator + Send> ParallelDrainRange<usize> for &'a mut ArcVec<T, A> {
+    type Iter = ArcVecParDrain<'a, T, A>;
+    type Item = T;
+
+    fn par_drain<R>(self, range: R) -> Self::Iter
+    where R: RangeBounds<usize>,
+    {
+        ArcVecParDrain { drain: self.drain(range) }
+    }
+}
+
+impl<T: Send, A: Allocator + Send> ParallelIterator for ArcVecParDrain<'_, T, A> {
+    type Item = T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.drain.len())
+    }
+}
+
+impl<T: Send, A: Allocator + Send> IndexedParallelIterator for ArcVecParDrain<'_, T, A> {
+    fn drive<C>(self, consumer: C) -> C::Result
+    where C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.drain.len()
+    }
+
+    fn with_producer<CB>(mut self, callback: CB) -> CB::Output
+    where CB: ProducerCallback<Self::Item>,
+    {
+        let slice = unsafe {
+            let len = self.drain.len();
+            let vec = self.drain.vec.as_mut();
+
+            // The pointer is derived from the buffer directly,
+            // the drained range is beyond `vec.len()`
+            let ptr = vec.as_mut_ptr().add(vec.len());
+
+            // The producer takes the elements, the drain only moves the tail back
+            self.drain.iter = Default::default();
+
+            slice::from_raw_parts_mut(ptr, len)
+        };
+
+        callback.callback(DrainProducer { slice })
+    }
+}
+
+/// Owns the elements of `slice`, moves or drops them
+struct DrainProducer<'a, T: Send> {
+    slice: &'a mut [T],
+}
+
+impl<'a, T: Send + 'a> Producer for DrainProducer<'a, T> {
+    type Item = T;
+    type IntoIter = SliceDrain<'a, T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let slice = mem::take(&mut self.slice);
+        SliceDrain { iter: slice.iter_mut() }
+    }
+
+    fn split_at(mut self, index: usize) -> (Self, Self) {
+        let slice = mem::take(&mut self.slice);
+        let (left, right) = slice.split_at_mut(index);
+        (DrainProducer { slice: left }, DrainProducer { slice: right })
+    }
+}
+
+impl<T: Send> Drop for DrainProducer<'_, T> {
+    fn drop(&mut self) {
+        let slice: *mut [T] = mem::take::<&mut [T]>(&mut self.slice);
+        unsafe { ptr::drop_in_place(slice) }
+    }
+}
+
+struct SliceDrain<'a, T> {
+    iter: slice::IterMut<'a, T>,
+}
+
+impl<T> Iterator for SliceDrain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ptr: *const T = self.iter.next()?;
+        Some(unsafe { ptr::read(ptr) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for SliceDrain<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let ptr: *const T = self.iter.next_back()?;
+        Some(unsafe { ptr::read(ptr) })
+    }
+}
+
+impl<T> ExactSizeIterator for SliceDrain<'_, T> { }
+
+impl<T> FusedIterator for SliceDrain<'_, T> { }
+
+impl<T> Drop for SliceDrain<'_, T> {
+    fn drop(&mut self) {
+        let iter = mem::take(&mut self.iter);
+        let slice: *mut [T] = iter.into_slice();
+        unsafe { ptr::drop_in_place(slice) }
+    }
+}
+
+impl<T: Send, A: Allocator> ParallelExtend<T> for ArcVec<T, A> {
+    fn par_extend<I>(&mut self, par_iter: I)
+    where I: IntoParallelIterator<Item = T>,
+    {
+        let list = par_iter.into_par_iter()
+            .fold(Vec::new, |mut vec, elem| {
+                vec.push(elem);
+                vec
+            })
+            .map(|vec| LinkedList::from([vec]))
+            .reduce(LinkedList::new, |mut list, mut other| {
+                list.append(&mut other);
+                list
+            });
+
+        self.reserve(list.iter().map(Vec::len).sum());
+
+        for vec in list {
+            self.extend(vec);
+        }
+    }
+}
+
+impl<'a, T: Copy + Send + Sync + 'a, A: Allocator> ParallelExtend<&'a T> for ArcVec<T, A> {
+    fn par_extend<I>(&mut self, par_iter: I)
+    where I: IntoParallelIterator<Item = &'a T>,
+    {
+        self.par_extend(par_iter.into_par_iter().copied());
+    }
+}
+
+impl<T: Send> FromParallelIterator<T> for ArcVec<T> {
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where I: IntoParallelIterator<Item = T>,
+    {
+        let mut vec = Self::new();
+        vec.par_extend(par_iter);
+        vec
+    }
+}
@@ -0,0 +1,329 @@
+#[cfg(doc)]
+use alloc::{rc::Rc, sync::Arc};
+
+use core::{
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::{Deref, DerefMut},
+    ptr, slice,
+};
+
+use crate::{
+    allocator::{Allocator, Global},
+    kind, RcKind,
+};
+
+use super::SharedVec;
+
+/// Like [`SharedVec`], but store up to `N` elements inline,
+/// spill into the reference counted buffer when it grows
+///
+/// Generic over the [`RcKind`], usually used through [`SmallRcVec`] or [`SmallArcVec`]
+///
+/// # Examples
+///
+/// ```
+/// # use std::rc::Rc;
+/// use rc_vec::SmallRcVec;
+///
+/// let mut vec: SmallRcVec<i32, 2> = SmallRcVec::new();
+/// vec.push(1);
+/// vec.push(2);
+/// assert!(! vec.spilled());
+///
+/// vec.push(3);
+/// assert!(vec.spilled());
+///
+/// let rc: Rc<[i32]> = vec.into_rc_slice();
+/// assert_eq!(*rc, [1, 2, 3]);
+/// ```
+pub struct SharedSmallVec<T, const N: usize, K: RcKind, A: Allocator = Global> {
+    /// Length of the inline elements, always `0` after spilled
+    len: usize,
+    inline: [MaybeUninit<T>; N],
+    /// Never allocated before spilled
+    heap: SharedVec<T, K, A>,
+}
+
+/// [`SharedSmallVec`] based on [`Rc`]
+pub type SmallRcVec<T, const N: usize, A = Global> = SharedSmallVec<T, N, kind::Rc, A>;
+
+/// [`SharedSmallVec`] based on [`Arc`]
+pub type SmallArcVec<T, const N: usize, A = Global> = SharedSmallVec<T, N, kind::Arc, A>;
+
+impl<T, const N: usize, K: RcKind, A: Allocator> Drop for SharedSmallVec<T, N, K, A> {
+    fn drop(&mut self) {
+        let inline = ptr::slice_from_raw_parts_mut(self.inline.as_mut_ptr().cast::<T>(), self.len);
+        unsafe { ptr::drop_in_place(inline) }
+    }
+}
+
+impl<T, const N: usize, K: RcKind> Default for SharedSmallVec<T, N, K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize, K: RcKind> SharedSmallVec<T, N, K> {
+    /// Create a new empty inline vector
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    /// Create a new vector, spilled if `capacity > N`
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, Global)
+    }
+
+    /// Freeze into an exact size [`Rc<[T]>`](Rc) or [`Arc<[T]>`](Arc)
+    ///
+    /// The inline elements are moved into a new allocation,
+    /// the spilled buffer is reused, see [`SharedVec::into_rc_slice`]
+    pub fn into_rc_slice(self) -> K::Rc<[T]> {
+        self.into_vec().into_rc_slice()
+    }
+}
+
+impl<T, const N: usize, K: RcKind, A: Allocator> SharedSmallVec<T, N, K, A> {
+    /// Like [`new`](#method.new), but the spilled buffer with the allocator `alloc`
+    pub const fn new_in(alloc: A) -> Self {
+        Self {
+            len: 0,
+            inline: [const { MaybeUninit::uninit() }; N],
+            heap: SharedVec::new_in(alloc),
+        }
+    }
+
+    /// Like [`with_capacity`](#method.with_capacity), but with the allocator `alloc`
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        let mut this = Self::new_in(alloc);
+        if capacity > N {
+            this.heap.reserve_exact(capacity);
+        }
+        this
+    }
+
+    /// Returns a reference to the underlying allocator
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.heap.allocator()
+    }
+
+    /// Returns `true` if the elements are stored in the reference counted buffer
+    ///
+    /// Zero sized elements are always spilled, without allocation
+    #[inline]
+    pub fn spilled(&self) -> bool {
+        self.heap.capacity() != 0
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        if self.spilled() { self.heap.len() } else { self.len }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        if self.spilled() { self.heap.capacity() } else { N }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        if self.spilled() {
+            &self.heap
+        } else {
+            unsafe { slice::from_raw_parts(self.inline.as_ptr().cast(), self.len) }
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        if self.spilled() {
+            &mut self.heap
+        } else {
+            unsafe { slice::from_raw_parts_mut(self.inline.as_mut_ptr().cast(), self.len) }
+        }
+    }
+
+    /// Move the inline elements into a new buffer with capacity of `capacity`
+    fn spill(&mut self, capacity: usize) {
+        debug_assert!(!self.spilled());
+        debug_assert!(capacity >= self.len);
+
+        self.heap.reserve_exact(capacity);
+
+        let len = self.len;
+        self.len = 0;
+        unsafe {
+            let src = self.inline.as_ptr().cast::<T>();
+            ptr::copy_nonoverlapping(src, self.heap.as_mut_ptr(), len);
+            self.heap.set_len(len);
+        }
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        if self.spilled() {
+            self.heap.reserve(additional);
+        } else if additional > N - self.len {
+            let required = self.len.checked_add(additional).expect("capacity overflow");
+            self.spill(required.max(N.saturating_mul(2)));
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        if self.spilled() {
+            self.heap.push(value);
+        } else if self.len < N {
+            self.inline[self.len].write(value);
+            self.len += 1;
+        } else {
+            self.spill(N.saturating_mul(2).max(4));
+            self.heap.push(value);
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.spilled() {
+            self.heap.pop()
+        } else if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            unsafe { Some(self.inline[self.len].assume_init_read()) }
+        }
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        if self.spilled() {
+            self.heap.truncate(len);
+        } else if len < self.len {
+            let old_len = self.len;
+            self.len = len;
+            unsafe {
+                let data = self.inline.as_mut_ptr().add(len).cast::<T>();
+                ptr::drop_in_place(ptr::slice_from_raw_parts_mut(data, old_len - len));
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Convert into [`SharedVec`], the inline elements are moved into
+    /// an exact size allocation
+    pub fn into_vec(mut self) -> SharedVec<T, K, A> {
+        if !self.spilled() && self.len != 0 {
+            self.spill(self.len);
+        }
+
+        let this = ManuallyDrop::new(self);
+        debug_assert_eq!(this.len, 0);
+        unsafe { ptr::read(&this.heap) }
+    }
+}
+
+impl<T, const N: usize, K: RcKind, A: Allocator> From<SharedVec<T, K, A>> for SharedSmallVec<T, N, K, A> {
+    /// Reuse the buffer of `vec`, spilled if it is allocated
+    fn from(vec: SharedVec<T, K, A>) -> Self {
+        Self { len: 0, inline: [const { MaybeUninit::uninit() }; N], heap: vec }
+    }
+}
+
+impl<T, const N: usize, K: RcKind, A: Allocator> From<SharedSmallVec<T, N, K, A>> for SharedVec<T, K, A> {
+    fn from(value: SharedSmallVec<T, N, K, A>) -> Self {
+        value.into_vec()
+    }
+}
+
+impl<T, const N: usize, K: RcKind, A: Allocator> Deref for SharedSmallVec<T, N, K, A> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize, K: RcKind, A: Allocator> DerefMut for SharedSmallVec<T, N, K, A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: Clone, const N: usize, K: RcKind, A: Allocator + Clone> Clone for SharedSmallVec<T, N, K, A> {
+    fn clone(&self) -> Self {
+        let mut vec = Self::with_capacity_in(self.len(), self.allocator().clone());
+        vec.extend(self.iter().cloned());
+        vec
+    }
+}
+
+impl<T: Debug, const N: usize, K: RcKind, A: Allocator> Debug for SharedSmallVec<T, N, K, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl<T: PartialEq, const N: usize, K: RcKind, A: Allocator> PartialEq for SharedSmallVec<T, N, K, A> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: PartialEq, const N: usize, K: RcKind, A: Allocator> PartialEq<[T]> for SharedSmallVec<T, N, K, A> {
+    fn eq(&self, other: &[T]) -> bool {
+        **self == *other
+    }
+}
+
+impl<T: PartialEq, const M: usize, const N: usize, K: RcKind, A: Allocator> PartialEq<[T; M]> for SharedSmallVec<T, N, K, A> {
+    fn eq(&self, other: &[T; M]) -> bool {
+        **self == *other
+    }
+}
+
+impl<T: Eq, const N: usize, K: RcKind, A: Allocator> Eq for SharedSmallVec<T, N, K, A> {}
+
+impl<T: Hash, const N: usize, K: RcKind, A: Allocator> Hash for SharedSmallVec<T, N, K, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl<T, const N: usize, K: RcKind, A: Allocator> Extend<T> for SharedSmallVec<T, N, K, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        iter.for_each(|elem| self.push(elem));
+    }
+}
+
+impl<T, const N: usize, K: RcKind> FromIterator<T> for SharedSmallVec<T, N, K> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<'a, T, const N: usize, K: RcKind, A: Allocator> IntoIterator for &'a SharedSmallVec<T, N, K, A> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize, K: RcKind, A: Allocator> IntoIterator for &'a mut SharedSmallVec<T, N, K, A> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
@@ -4,119 +4,113 @@ use core::{
     fmt::{self, Debug},
     hash::{self, Hash},
     ops::{Index, IndexMut},
-    ptr,
     slice::SliceIndex,
 };
 use rc_vec_proc_macro::rc_impl_gen_arc_impl;
 use unique_rc::{UniqArc, UniqRc};
 
-use crate::{ArcVec, RcVec};
+use crate::{
+    allocator::{Allocator, Global},
+    growth::GrowthPolicy,
+    ArcString, ArcVec, RcKind, RcString, RcVec, SharedVec,
+};
 
-mod into_iter;
+pub(super) mod into_iter;
 
-#[rc_impl_gen_arc_impl]
-impl<T> AsRef<Self> for RcVec<T> {
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> AsRef<Self> for SharedVec<T, K, A, G> {
     fn as_ref(&self) -> &Self {
         self
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> AsMut<Self> for RcVec<T> {
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> AsMut<Self> for SharedVec<T, K, A, G> {
     fn as_mut(&mut self) -> &mut Self {
         self
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> AsRef<[T]> for RcVec<T> {
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> AsRef<[T]> for SharedVec<T, K, A, G> {
     fn as_ref(&self) -> &[T] {
         self
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> AsMut<[T]> for RcVec<T> {
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> AsMut<[T]> for SharedVec<T, K, A, G> {
     fn as_mut(&mut self) -> &mut [T] {
         self
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> Borrow<[T]> for RcVec<T> {
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> Borrow<[T]> for SharedVec<T, K, A, G> {
     fn borrow(&self) -> &[T] {
         self
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> BorrowMut<[T]> for RcVec<T> {
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> BorrowMut<[T]> for SharedVec<T, K, A, G> {
     fn borrow_mut(&mut self) -> &mut [T] {
         self
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: Clone> From<&[T]> for RcVec<T> {
+impl<T: Clone, K: RcKind> From<&[T]> for SharedVec<T, K> {
     fn from(value: &[T]) -> Self {
-        let uniq_rc: UniqRc<[T]> = UniqRc::from(value);
-        Self::from(uniq_rc)
+        let uniq_rc = K::uniq_slice_from_iter(value.iter().cloned());
+
+        #[cfg(feature = "stats")]
+        crate::stats::record_allocation();
+
+        Self::from_uniq_slice(uniq_rc)
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: Clone> From<&mut [T]> for RcVec<T> {
+impl<T: Clone, K: RcKind> From<&mut [T]> for SharedVec<T, K> {
     fn from(value: &mut [T]) -> Self {
-        let uniq_rc: UniqRc<[T]> = UniqRc::from(value);
-        Self::from(uniq_rc)
+        Self::from(&*value)
     }
 }
 
+// `K::Rc<[T]>` cannot be used in the generic impls, it may overlap with the other impls,
+// the conversions with the pointers are implemented for each kind,
+// use the generic methods instead, e.g `SharedVec::from_rc_or_clone`
 #[rc_impl_gen_arc_impl]
 impl<T: Clone> From<Rc<[T]>> for RcVec<T> {
     fn from(value: Rc<[T]>) -> Self {
-        let uniq_rc = UniqRc::from(value);
-        Self::from(uniq_rc)
+        Self::from_rc_or_clone(value).0
     }
 }
 
 #[rc_impl_gen_arc_impl]
 impl From<UniqRc<str>> for RcVec<u8> {
     fn from(value: UniqRc<str>) -> Self {
-        let len = value.len();
-        let rc = UniqRc::into_raw(value).cast::<u8>();
-        let slice = ptr::slice_from_raw_parts_mut(rc, len);
-        let value = unsafe { UniqRc::from_raw_unchecked(slice) };
-        value.into()
+        RcString::from_uniq_str(value).into_bytes()
     }
 }
 
 #[rc_impl_gen_arc_impl]
 impl From<Rc<str>> for RcVec<u8> {
     fn from(value: Rc<str>) -> Self {
-        UniqRc::new(value).into()
+        RcString::from_rc_str(value).into_bytes()
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T, const N: usize> From<[T; N]> for RcVec<T> {
+impl<T, const N: usize, K: RcKind> From<[T; N]> for SharedVec<T, K> {
     fn from(value: [T; N]) -> Self {
         Self::from_iter(value)
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> FromIterator<T> for RcVec<T> {
+impl<T, K: RcKind, G: GrowthPolicy> FromIterator<T> for SharedVec<T, K, Global, G> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let iter = iter.into_iter();
-        let mut buf = Self::with_capacity(iter.size_hint().0);
+        let mut buf = SharedVec::with_capacity(iter.size_hint().0)
+            .with_growth_policy();
         buf.extend(iter);
         buf
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> Extend<T> for RcVec<T> {
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> Extend<T> for SharedVec<T, K, A, G> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         let iter = iter.into_iter();
         self.reserve(iter.size_hint().0);
@@ -124,173 +118,162 @@ impl<T> Extend<T> for RcVec<T> {
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<'a, T: Copy> Extend<&'a T> for RcVec<T> {
+/// Copies the elements one by one like `Extend<T>`, only the length is updated once,
+/// use [`extend_from_copy_slice`](SharedVec::extend_from_copy_slice) to copy a slice at once
+impl<'a, T: Copy, K: RcKind, A: Allocator, G: GrowthPolicy> Extend<&'a T> for SharedVec<T, K, A, G> {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
-        self.extend(iter.into_iter().copied());
+        let mut iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        self.reserve(lower);
+
+        // `T: Copy` has no drop, so the length is only updated once
+        let len = self.len();
+        let written = self.spare_capacity_mut().iter_mut()
+            .zip(iter.by_ref().take(lower))
+            .map(|(dst, src)| dst.write(*src))
+            .count();
+        unsafe { self.set_len(len + written) };
+
+        iter.for_each(|value| self.push(*value));
     }
 }
 
 #[rc_impl_gen_arc_impl]
-impl<T> From<RcVec<T>> for Rc<[T]> {
-    fn from(value: RcVec<T>) -> Self {
+impl<T, G: GrowthPolicy> From<RcVec<T, Global, G>> for Rc<[T]> {
+    fn from(value: RcVec<T, Global, G>) -> Self {
         value.into_rc_slice()
     }
 }
 
 // 这将产生分配和拷贝
-#[rc_impl_gen_arc_impl]
-impl<T> From<RcVec<T>> for Box<[T]> {
-    fn from(value: RcVec<T>) -> Self {
-        let len = value.len();
-        let raw = value.into_raw_vec();
-        Box::from_iter(raw.slice()[..len].iter().map(|value| {
-            unsafe { value.assume_init_read() }
-        }))
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> From<SharedVec<T, K, A, G>> for Box<[T]> {
+    fn from(value: SharedVec<T, K, A, G>) -> Self {
+        #[cfg(feature = "stats")]
+        {
+            // `Box<[T]>` does not allocate when empty
+            if !value.is_empty() && size_of::<T>() != 0 {
+                crate::stats::record_allocation();
+            }
+            crate::stats::record_conversion(false);
+        }
+
+        // the buffer of zero sized elements is not allocated, move them by the iterator
+        Box::from_iter(value)
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl From<&str> for RcVec<u8> {
+impl<K: RcKind> From<&str> for SharedVec<u8, K> {
     fn from(value: &str) -> Self {
-        let uniq_rc: UniqRc<str> = UniqRc::from(value);
-        let len = uniq_rc.len();
-        let str_ptr = UniqRc::into_raw(uniq_rc);
-        let uniq_rc = unsafe {
-            let slice_ptr = ptr::slice_from_raw_parts_mut(
-                str_ptr.cast::<u8>(),
-                len,
-            );
-            UniqRc::from_raw_unchecked(slice_ptr)
-        };
-        Self::from(uniq_rc)
+        Self::from(value.as_bytes())
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: Eq> Eq for RcVec<T> {}
+impl<T: Eq, K: RcKind, A: Allocator, G: GrowthPolicy> Eq for SharedVec<T, K, A, G> {}
 
-#[rc_impl_gen_arc_impl]
-impl<T: PartialEq> PartialEq for RcVec<T> {
+impl<T: PartialEq, K: RcKind, A: Allocator, G: GrowthPolicy> PartialEq for SharedVec<T, K, A, G> {
     fn eq(&self, other: &Self) -> bool {
         **self == **other
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: PartialEq> PartialEq<[T]> for RcVec<T> {
+impl<T: PartialEq, K: RcKind, A: Allocator, G: GrowthPolicy> PartialEq<[T]> for SharedVec<T, K, A, G> {
     fn eq(&self, other: &[T]) -> bool {
         **self == *other
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: PartialEq> PartialEq<RcVec<T>> for Vec<T> {
-    fn eq(&self, other: &RcVec<T>) -> bool {
+impl<T: PartialEq, K: RcKind, A: Allocator, G: GrowthPolicy> PartialEq<SharedVec<T, K, A, G>> for Vec<T> {
+    fn eq(&self, other: &SharedVec<T, K, A, G>) -> bool {
         **self == **other
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: PartialEq> PartialEq<Vec<T>> for RcVec<T> {
+impl<T: PartialEq, K: RcKind, A: Allocator, G: GrowthPolicy> PartialEq<Vec<T>> for SharedVec<T, K, A, G> {
     fn eq(&self, other: &Vec<T>) -> bool {
         **self == **other
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: PartialEq, const N: usize> PartialEq<[T; N]> for RcVec<T> {
+impl<T: PartialEq, const N: usize, K: RcKind, A: Allocator, G: GrowthPolicy> PartialEq<[T; N]> for SharedVec<T, K, A, G> {
     fn eq(&self, other: &[T; N]) -> bool {
         **self == *other
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: PartialEq, const N: usize> PartialEq<&[T; N]> for RcVec<T> {
+impl<T: PartialEq, const N: usize, K: RcKind, A: Allocator, G: GrowthPolicy> PartialEq<&[T; N]> for SharedVec<T, K, A, G> {
     fn eq(&self, other: &&[T; N]) -> bool {
         **self == **other
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: PartialEq, const N: usize> PartialEq<&mut [T; N]> for RcVec<T> {
+impl<T: PartialEq, const N: usize, K: RcKind, A: Allocator, G: GrowthPolicy> PartialEq<&mut [T; N]> for SharedVec<T, K, A, G> {
     fn eq(&self, other: &&mut [T; N]) -> bool {
         **self == **other
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: PartialEq> PartialEq<&[T]> for RcVec<T> {
+impl<T: PartialEq, K: RcKind, A: Allocator, G: GrowthPolicy> PartialEq<&[T]> for SharedVec<T, K, A, G> {
     fn eq(&self, other: &&[T]) -> bool {
         **self == **other
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: PartialEq> PartialEq<&mut [T]> for RcVec<T> {
+impl<T: PartialEq, K: RcKind, A: Allocator, G: GrowthPolicy> PartialEq<&mut [T]> for SharedVec<T, K, A, G> {
     fn eq(&self, other: &&mut [T]) -> bool {
         **self == **other
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: PartialEq> PartialEq<RcVec<T>> for [T] {
-    fn eq(&self, other: &RcVec<T>) -> bool {
+impl<T: PartialEq, K: RcKind, A: Allocator, G: GrowthPolicy> PartialEq<SharedVec<T, K, A, G>> for [T] {
+    fn eq(&self, other: &SharedVec<T, K, A, G>) -> bool {
         *self == **other
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: PartialEq> PartialEq<RcVec<T>> for &[T] {
-    fn eq(&self, other: &RcVec<T>) -> bool {
+impl<T: PartialEq, K: RcKind, A: Allocator, G: GrowthPolicy> PartialEq<SharedVec<T, K, A, G>> for &[T] {
+    fn eq(&self, other: &SharedVec<T, K, A, G>) -> bool {
         **self == **other
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: PartialEq> PartialEq<RcVec<T>> for &mut [T] {
-    fn eq(&self, other: &RcVec<T>) -> bool {
+impl<T: PartialEq, K: RcKind, A: Allocator, G: GrowthPolicy> PartialEq<SharedVec<T, K, A, G>> for &mut [T] {
+    fn eq(&self, other: &SharedVec<T, K, A, G>) -> bool {
         **self == **other
     }
 }
 
 #[rc_impl_gen_arc_impl]
-impl<T: PartialEq> PartialEq<UniqRc<[T]>> for RcVec<T> {
+impl<T: PartialEq, A: Allocator, G: GrowthPolicy> PartialEq<UniqRc<[T]>> for RcVec<T, A, G> {
     fn eq(&self, other: &UniqRc<[T]>) -> bool {
         **self == **other
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: Debug> Debug for RcVec<T> {
+impl<T: Debug, K: RcKind, A: Allocator, G: GrowthPolicy> Debug for SharedVec<T, K, A, G> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         (**self).fmt(f)
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: Hash> Hash for RcVec<T> {
+impl<T: Hash, K: RcKind, A: Allocator, G: GrowthPolicy> Hash for SharedVec<T, K, A, G> {
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
         (**self).hash(state);
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: PartialOrd> PartialOrd for RcVec<T> {
+impl<T: PartialOrd, K: RcKind, A: Allocator, G: GrowthPolicy> PartialOrd for SharedVec<T, K, A, G> {
     fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         (**self).partial_cmp(other)
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T: Ord> Ord for RcVec<T> {
+impl<T: Ord, K: RcKind, A: Allocator, G: GrowthPolicy> Ord for SharedVec<T, K, A, G> {
     fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         (**self).cmp(other)
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T, I: SliceIndex<[T]>> Index<I> for RcVec<T> {
+impl<T, I: SliceIndex<[T]>, K: RcKind, A: Allocator, G: GrowthPolicy> Index<I> for SharedVec<T, K, A, G> {
     type Output = I::Output;
 
     fn index(&self, index: I) -> &Self::Output {
@@ -298,15 +281,13 @@ impl<T, I: SliceIndex<[T]>> Index<I> for RcVec<T> {
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T, I: SliceIndex<[T]>> IndexMut<I> for RcVec<T> {
+impl<T, I: SliceIndex<[T]>, K: RcKind, A: Allocator, G: GrowthPolicy> IndexMut<I> for SharedVec<T, K, A, G> {
     fn index_mut(&mut self, index: I) -> &mut Self::Output {
         &mut (**self)[index]
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<'a, T> IntoIterator for &'a RcVec<T> {
+impl<'a, T, K: RcKind, A: Allocator, G: GrowthPolicy> IntoIterator for &'a SharedVec<T, K, A, G> {
     type Item = &'a T;
     type IntoIter = <&'a [T] as IntoIterator>::IntoIter;
 
@@ -315,8 +296,7 @@ impl<'a, T> IntoIterator for &'a RcVec<T> {
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<'a, T> IntoIterator for &'a mut RcVec<T> {
+impl<'a, T, K: RcKind, A: Allocator, G: GrowthPolicy> IntoIterator for &'a mut SharedVec<T, K, A, G> {
     type Item = &'a mut T;
     type IntoIter = <&'a mut [T] as IntoIterator>::IntoIter;
 
@@ -325,22 +305,20 @@ impl<'a, T> IntoIterator for &'a mut RcVec<T> {
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> IntoIterator for RcVec<T> {
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> IntoIterator for SharedVec<T, K, A, G> {
     type Item = T;
-    type IntoIter = into_iter::RcVecIntoIter<T>;
+    type IntoIter = into_iter::SharedVecIntoIter<T, K, A, G>;
 
     fn into_iter(self) -> Self::IntoIter {
         let len = self.len();
-        into_iter::RcVecIntoIter::new(self.into_raw_vec(), len)
+        into_iter::SharedVecIntoIter::new(self.into_raw_vec(), len)
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T, const N: usize> TryFrom<RcVec<T>> for [T; N] {
-    type Error = RcVec<T>;
+impl<T, const N: usize, K: RcKind, A: Allocator, G: GrowthPolicy> TryFrom<SharedVec<T, K, A, G>> for [T; N] {
+    type Error = SharedVec<T, K, A, G>;
 
-    fn try_from(mut vec: RcVec<T>) -> Result<Self, Self::Error> {
+    fn try_from(mut vec: SharedVec<T, K, A, G>) -> Result<Self, Self::Error> {
         if vec.len() != N {
             return Err(vec);
         }
@@ -354,26 +332,20 @@ impl<T, const N: usize> TryFrom<RcVec<T>> for [T; N] {
 }
 
 #[rc_impl_gen_arc_impl]
-impl<T, const N: usize> TryFrom<RcVec<T>> for Rc<[T; N]> {
-    type Error = RcVec<T>;
-
-    fn try_from(vec: RcVec<T>) -> Result<Self, Self::Error> {
-        if vec.len() != N {
-            return Err(vec);
-        }
+impl<T, const N: usize, G: GrowthPolicy> TryFrom<RcVec<T, Global, G>> for Rc<[T; N]> {
+    type Error = RcVec<T, Global, G>;
 
-        let slice = Rc::<[T]>::from(vec);
-        Ok(slice.try_into().ok().unwrap())
+    fn try_from(vec: RcVec<T, Global, G>) -> Result<Self, Self::Error> {
+        vec.try_into_rc_array()
     }
 }
 
-#[rc_impl_gen_arc_impl]
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
-impl std::io::Write for RcVec<u8> {
+impl<K: RcKind, A: Allocator, G: GrowthPolicy> std::io::Write for SharedVec<u8, K, A, G> {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.extend_from_slice(buf);
+        self.extend_from_copy_slice(buf);
         Ok(buf.len())
     }
 
@@ -382,14 +354,14 @@ impl std::io::Write for RcVec<u8> {
         let len = bufs.iter().map(|b| b.len()).sum();
         self.reserve(len);
         for buf in bufs {
-            self.extend_from_slice(buf);
+            self.extend_from_copy_slice(buf);
         }
         Ok(len)
     }
 
     #[inline]
     fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
-        self.extend_from_slice(buf);
+        self.extend_from_copy_slice(buf);
         Ok(())
     }
 
@@ -399,24 +371,131 @@ impl std::io::Write for RcVec<u8> {
     }
 }
 
-#[rc_impl_gen_arc_impl]
+impl<K: RcKind, A: Allocator, G: GrowthPolicy> fmt::Write for SharedVec<u8, K, A, G> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.extend_from_copy_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embedded_io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded_io")))]
+impl<K: RcKind, A: Allocator, G: GrowthPolicy> embedded_io::ErrorType for SharedVec<u8, K, A, G> {
+    type Error = core::convert::Infallible;
+}
+
+#[cfg(feature = "embedded_io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded_io")))]
+impl<K: RcKind, A: Allocator, G: GrowthPolicy> embedded_io::Write for SharedVec<u8, K, A, G> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.extend_from_copy_slice(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        self.extend_from_copy_slice(buf);
+        Ok(())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+unsafe impl<K: RcKind, A: Allocator, G: GrowthPolicy> bytes::BufMut for SharedVec<u8, K, A, G> {
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        isize::MAX as usize - self.len()
+    }
+
+    #[inline]
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        let len = self.len();
+        let remaining = self.capacity() - len;
+
+        if remaining < cnt {
+            panic!("advance out of bounds: the len is {remaining} but advancing by {cnt}");
+        }
+
+        self.set_len(len + cnt);
+    }
+
+    #[inline]
+    fn chunk_mut(&mut self) -> &mut bytes::buf::UninitSlice {
+        if self.capacity() == self.len() {
+            self.reserve(64);
+        }
+
+        bytes::buf::UninitSlice::uninit(self.spare_capacity_mut())
+    }
+
+    #[inline]
+    fn put_slice(&mut self, src: &[u8]) {
+        self.extend_from_copy_slice(src);
+    }
+}
+
+/// Keeps the allocation, without copy
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl<A: Allocator + Send + 'static, G: GrowthPolicy + 'static> From<ArcVec<u8, A, G>> for bytes::Bytes {
+    fn from(value: ArcVec<u8, A, G>) -> Self {
+        bytes::Bytes::from_owner(value)
+    }
+}
+
+/// Decodes a sequence element by element, like `Vec<T>`
+///
+/// For bytes, opt in to [`serde_as_bytes`](crate::serde_as_bytes)
+/// by `#[serde(with = "rc_vec::serde_as_bytes")]`,
+/// which copies the whole buffer at once in binary formats
 #[cfg(feature = "serde")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
-impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for RcVec<T> {
+impl<'de, T: serde::Deserialize<'de>, K: RcKind> serde::Deserialize<'de> for SharedVec<T, K> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where D: serde::Deserializer<'de>,
     {
-        let boxed: Box<[T]> = serde::Deserialize::deserialize(deserializer)?;
-        let rc = Rc::from(boxed);
-        let uniq: UniqRc<[T]> = UniqRc::try_new(rc).ok().unwrap();
-        Ok(uniq.into())
+        use core::marker::PhantomData;
+        use crate::utils;
+
+        struct SeqVisitor<T, K>(PhantomData<(T, K)>);
+
+        impl<'de, T, K> serde::de::Visitor<'de> for SeqVisitor<T, K>
+        where T: serde::Deserialize<'de>,
+              K: RcKind,
+        {
+            type Value = SharedVec<T, K>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a sequence")
+            }
+
+            fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+            where S: serde::de::SeqAccess<'de>,
+            {
+                let capacity = utils::cautious_capacity::<T>(seq.size_hint());
+                let mut vec = SharedVec::with_capacity(capacity);
+
+                while let Some(value) = seq.next_element()? {
+                    vec.push(value);
+                }
+                Ok(vec)
+            }
+        }
+
+        deserializer.deserialize_seq(SeqVisitor(PhantomData))
     }
 }
 
-#[rc_impl_gen_arc_impl]
 #[cfg(feature = "serde")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
-impl<T: serde::Serialize> serde::Serialize for RcVec<T> {
+impl<T: serde::Serialize, K: RcKind, A: Allocator, G: GrowthPolicy> serde::Serialize for SharedVec<T, K, A, G> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where S: serde::Serializer,
     {
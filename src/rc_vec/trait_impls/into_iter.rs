@@ -1,24 +1,32 @@
+use core::alloc::Layout;
 use core::fmt::{self, Debug};
 use core::iter::FusedIterator;
 use core::mem;
 use core::{ptr, slice};
 
-use rc_vec_proc_macro::rc_impl_gen_arc_impl;
-
-use crate::raw::{ArcRawVec, RcRawVec};
+use crate::raw::RawVec;
+use crate::{
+    allocator::{Allocator, Global},
+    growth::{Doubling, GrowthPolicy},
+    kind, RcKind, SharedVec,
+};
 
 use crate::is_zst::IsZst;
 
-#[rc_impl_gen_arc_impl]
-pub struct RcVecIntoIter<T> {
-    _raw: Option<RcRawVec<T>>,
+pub struct SharedVecIntoIter<T, K: RcKind, A: Allocator = Global, G: GrowthPolicy = Doubling> {
+    raw: RawVec<T, K, A, G>,
     ptr: *const T,
     end: *const T,
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> RcVecIntoIter<T> {
-    pub fn new(mut raw: RcRawVec<T>, len: usize) -> Self {
+/// [`SharedVecIntoIter`] of [`RcVec`](crate::RcVec)
+pub type RcVecIntoIter<T, A = Global, G = Doubling> = SharedVecIntoIter<T, kind::Rc, A, G>;
+
+/// [`SharedVecIntoIter`] of [`ArcVec`](crate::ArcVec)
+pub type ArcVecIntoIter<T, A = Global, G = Doubling> = SharedVecIntoIter<T, kind::Arc, A, G>;
+
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> SharedVecIntoIter<T, K, A, G> {
+    pub fn new(mut raw: RawVec<T, K, A, G>, len: usize) -> Self {
         let ptr = raw.as_mut_ptr().cast_const().cast::<T>();
         let end = if T::ZST {
             ptr.wrapping_byte_add(len)
@@ -26,7 +34,7 @@ impl<T> RcVecIntoIter<T> {
             unsafe { ptr.add(len) }
         };
 
-        Self { _raw: Some(raw), ptr, end }
+        Self { raw, ptr, end }
     }
 
     pub fn as_slice(&self) -> &[T] {
@@ -46,28 +54,104 @@ impl<T> RcVecIntoIter<T> {
     }
 }
 
-#[rc_impl_gen_arc_impl]
-unsafe impl<T: Sync> Sync for RcVecIntoIter<T> { }
+impl<T, K: RcKind, A: Allocator + Clone, G: GrowthPolicy> SharedVecIntoIter<T, K, A, G> {
+    /// Like `self.map(f).collect()`,
+    /// but reuse the buffer when the layout of `T` and `U` are the same
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::{rc_vec, RcVec};
+    /// let vec = rc_vec![1u32, 2, 3];
+    /// let ptr = vec.as_ptr();
+    ///
+    /// let vec: RcVec<i32> = vec.into_iter().map_in_place(|n| -(n as i32));
+    /// assert_eq!(vec, [-1, -2, -3]);
+    /// assert_eq!(vec.as_ptr().cast(), ptr);
+    /// ```
+    pub fn map_in_place<U, F>(self, mut f: F) -> SharedVec<U, K, A, G>
+    where F: FnMut(T) -> U,
+    {
+        self.filter_map_in_place(|elem| Some(f(elem)))
+    }
+
+    /// Like `self.filter_map(f).collect()`,
+    /// but reuse the buffer when the layout of `T` and `U` are the same
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rc_vec::{rc_vec, RcVec};
+    /// let vec = rc_vec!["1", "x", "3"];
+    /// let vec: RcVec<i32> = vec.into_iter()
+    ///     .filter_map_in_place(|s| s.parse().ok());
+    /// assert_eq!(vec, [1, 3]);
+    /// ```
+    pub fn filter_map_in_place<U, F>(mut self, mut f: F) -> SharedVec<U, K, A, G>
+    where F: FnMut(T) -> Option<U>,
+    {
+        if Layout::new::<T>() != Layout::new::<U>() {
+            let alloc = self.raw.allocator().clone();
+            let mut vec = SharedVec::with_capacity_in(self.len(), alloc)
+                .with_growth_policy();
+            vec.extend(self.filter_map(f));
+            return vec;
+        }
 
-#[rc_impl_gen_arc_impl]
-unsafe impl<T: Send> Send for RcVecIntoIter<T> { }
+        /// Drop the written elements, when `f` panics
+        struct Written<U> {
+            buf: *mut U,
+            len: usize,
+        }
 
-#[rc_impl_gen_arc_impl]
-impl<T> AsRef<[T]> for RcVecIntoIter<T> {
+        impl<U> Drop for Written<U> {
+            fn drop(&mut self) {
+                let slice = ptr::slice_from_raw_parts_mut(self.buf, self.len);
+                unsafe { ptr::drop_in_place(slice) }
+            }
+        }
+
+        let mut written = Written {
+            buf: self.raw.as_mut_ptr().cast::<U>(),
+            len: 0,
+        };
+
+        // The written range is always before the unread elements
+        for elem in self.by_ref() {
+            if let Some(value) = f(elem) {
+                unsafe { written.buf.add(written.len).write(value) };
+                written.len += 1;
+            }
+        }
+
+        let len = written.len;
+        mem::forget(written);
+
+        let this = mem::ManuallyDrop::new(self);
+        let raw = unsafe { ptr::read(&this.raw).cast::<U>() };
+
+        SharedVec { raw, len }
+    }
+}
+
+// Only the Arc variant, the Rc variant follows `RcVec` and stays on its thread
+unsafe impl<T: Sync, A: Allocator + Sync, G: GrowthPolicy> Sync for ArcVecIntoIter<T, A, G> { }
+
+unsafe impl<T: Send, A: Allocator + Send, G: GrowthPolicy> Send for ArcVecIntoIter<T, A, G> { }
+
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> AsRef<[T]> for SharedVecIntoIter<T, K, A, G> {
     fn as_ref(&self) -> &[T] {
         self.as_slice()
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> Drop for RcVecIntoIter<T> {
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> Drop for SharedVecIntoIter<T, K, A, G> {
     fn drop(&mut self) {
         unsafe { ptr::drop_in_place(self.as_raw_mut_slice()) };
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> Iterator for RcVecIntoIter<T> {
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> Iterator for SharedVecIntoIter<T, K, A, G> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -102,8 +186,7 @@ impl<T> Iterator for RcVecIntoIter<T> {
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> DoubleEndedIterator for RcVecIntoIter<T> {
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> DoubleEndedIterator for SharedVecIntoIter<T, K, A, G> {
     fn next_back(&mut self) -> Option<Self::Item> {
         if self.ptr == self.end {
             None
@@ -119,24 +202,61 @@ impl<T> DoubleEndedIterator for RcVecIntoIter<T> {
     }
 }
 
-#[rc_impl_gen_arc_impl]
-impl<T> FusedIterator for RcVecIntoIter<T> { }
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> FusedIterator for SharedVecIntoIter<T, K, A, G> { }
 
-#[rc_impl_gen_arc_impl]
-impl<T> ExactSizeIterator for RcVecIntoIter<T> { }
+impl<T, K: RcKind, A: Allocator, G: GrowthPolicy> ExactSizeIterator for SharedVecIntoIter<T, K, A, G> { }
 
-#[rc_impl_gen_arc_impl]
-impl<T> Default for RcVecIntoIter<T> {
+impl<T, K: RcKind> Default for SharedVecIntoIter<T, K> {
     fn default() -> Self {
         let ptr = ptr::dangling();
-        Self { _raw: None, ptr, end: ptr }
+        Self { raw: RawVec::new(), ptr, end: ptr }
     }
 }
 
-impl<T: Debug> Debug for RcVecIntoIter<T> {
+impl<T: Debug, K: RcKind, A: Allocator, G: GrowthPolicy> Debug for SharedVecIntoIter<T, K, A, G> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("RcVecIntoIter")
+        f.debug_tuple("SharedVecIntoIter")
             .field(&self.as_slice())
             .finish()
     }
 }
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl<K: RcKind, A: Allocator, G: GrowthPolicy> bytes::Buf for SharedVecIntoIter<u8, K, A, G> {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        self.as_slice()
+    }
+
+    #[inline]
+    fn advance(&mut self, cnt: usize) {
+        let remaining = self.len();
+        assert!(cnt <= remaining,
+                "cannot advance past `remaining`: {cnt} <= {remaining}");
+
+        self.ptr = unsafe { self.ptr.add(cnt) };
+    }
+}
+
+#[cfg(feature = "embedded_io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded_io")))]
+impl<K: RcKind, A: Allocator, G: GrowthPolicy> embedded_io::ErrorType for SharedVecIntoIter<u8, K, A, G> {
+    type Error = core::convert::Infallible;
+}
+
+#[cfg(feature = "embedded_io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded_io")))]
+impl<K: RcKind, A: Allocator, G: GrowthPolicy> embedded_io::Read for SharedVecIntoIter<u8, K, A, G> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let n = buf.len().min(self.len());
+        buf[..n].copy_from_slice(&self.as_slice()[..n]);
+        self.ptr = unsafe { self.ptr.add(n) };
+        Ok(n)
+    }
+}
@@ -0,0 +1,95 @@
+//! Serialize and deserialize [`SharedVec<u8, K>`] as bytes,
+//! use it with `#[serde(with = "rc_vec::serde_as_bytes")]`
+//!
+//! The [`Deserialize`](serde::Deserialize) of [`RcVec<u8>`](crate::RcVec)
+//! decodes a sequence element by element,
+//! this module uses `deserialize_bytes`, so binary formats copy the buffer at once
+//!
+//! Unlike the `serde_bytes` crate, this module only supports [`SharedVec<u8, K>`]
+//!
+//! # Examples
+//!
+//! ```
+//! use rc_vec::RcVec;
+//! use serde::{Deserialize, Serialize};
+//! use serde_test::{assert_tokens, Token};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Packet {
+//!     #[serde(with = "rc_vec::serde_as_bytes")]
+//!     payload: RcVec<u8>,
+//! }
+//!
+//! let packet = Packet { payload: RcVec::from(b"rc-vec".as_slice()) };
+//! assert_tokens(&packet, &[
+//!     Token::Struct { name: "Packet", len: 1 },
+//!     Token::Str("payload"),
+//!     Token::Bytes(b"rc-vec"),
+//!     Token::StructEnd,
+//! ]);
+//! ```
+
+use core::{fmt, marker::PhantomData};
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::Serializer;
+
+use crate::{allocator::Allocator, utils, RcKind, SharedVec};
+
+/// Serialize the bytes by [`Serializer::serialize_bytes`]
+pub fn serialize<S, K, A>(
+    bytes: &SharedVec<u8, K, A>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where S: Serializer,
+      K: RcKind,
+      A: Allocator,
+{
+    serializer.serialize_bytes(bytes)
+}
+
+/// Deserialize the bytes by [`Deserializer::deserialize_bytes`],
+/// also accepts strings and sequences
+pub fn deserialize<'de, D, K>(deserializer: D) -> Result<SharedVec<u8, K>, D::Error>
+where D: Deserializer<'de>,
+      K: RcKind,
+{
+    deserializer.deserialize_bytes(BytesVisitor(PhantomData))
+}
+
+struct BytesVisitor<K>(PhantomData<K>);
+
+impl<'de, K: RcKind> Visitor<'de> for BytesVisitor<K> {
+    type Value = SharedVec<u8, K>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a byte array")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        let mut vec = SharedVec::with_capacity(v.len());
+        vec.extend_from_copy_slice(v);
+        Ok(vec)
+    }
+
+    /// The allocation of [`Vec`](alloc::vec::Vec) has no reference counts,
+    /// so it cannot be reused, the bytes are copied at once and `v` is freed
+    fn visit_byte_buf<E: de::Error>(self, v: alloc::vec::Vec<u8>) -> Result<Self::Value, E> {
+        self.visit_bytes(&v)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        self.visit_bytes(v.as_bytes())
+    }
+
+    fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+    where S: SeqAccess<'de>,
+    {
+        let capacity = utils::cautious_capacity::<u8>(seq.size_hint());
+        let mut vec = SharedVec::with_capacity(capacity);
+
+        while let Some(byte) = seq.next_element()? {
+            vec.push(byte);
+        }
+        Ok(vec)
+    }
+}
@@ -0,0 +1,94 @@
+//! Allocation and copy counters of the current thread, enabled by the `stats` feature
+//!
+//! The buffer is grown and shrunk by `realloc`, which copies the data
+//! only when the allocation is moved,
+//! and the conversions from a shared `Rc<[T]>` clone the elements,
+//! these counters make the costs visible, e.g in tests and benchmarks
+//!
+//! # Examples
+//!
+//! ```
+//! use rc_vec::{stats, RcVec};
+//! use std::rc::Rc;
+//!
+//! stats::reset();
+//!
+//! let mut vec = RcVec::with_capacity(2);
+//! vec.extend([1, 2, 3]);
+//! let rc: Rc<[i32]> = vec.into_rc_slice();
+//!
+//! let shared = rc.clone();
+//! let (_vec, reused) = RcVec::from_rc_or_clone(rc);
+//! assert!(! reused);
+//!
+//! let stats = stats::snapshot();
+//! assert_eq!(stats.allocations, 2);
+//! assert_eq!(stats.reallocations, 2);
+//! assert_eq!(stats.zero_copy_conversions, 1);
+//! assert_eq!(stats.copying_conversions, 1);
+//! # drop(shared);
+//! ```
+
+use core::cell::Cell;
+
+/// Snapshot of the counters, see [`snapshot`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Stats {
+    /// New buffers, including the clone of the elements
+    /// and the `Box<[T]>` of `From<RcVec<T>> for Box<[T]>`
+    pub allocations: usize,
+    /// Grow and shrink of the unique buffer,
+    /// e.g `reserve`, `push`, `shrink_to_fit` and `into_rc_slice`
+    pub reallocations: usize,
+    /// Bytes of the initialized elements copied when the grow or shrink
+    /// moved the buffer, `len * size_of::<T>()`,
+    /// the reallocations in place count zero
+    pub copied_bytes: usize,
+    /// Conversions that reused the buffer, e.g `into_rc_slice` and `try_from_rc`
+    pub zero_copy_conversions: usize,
+    /// Conversions that cloned or moved the elements into a new allocation,
+    /// e.g `From<Rc<[T]>>` of a shared `Rc` and `From<RcVec<T>> for Box<[T]>`
+    pub copying_conversions: usize,
+}
+
+std::thread_local! {
+    static STATS: Cell<Stats> = const { Cell::new(Stats {
+        allocations: 0,
+        reallocations: 0,
+        copied_bytes: 0,
+        zero_copy_conversions: 0,
+        copying_conversions: 0,
+    }) };
+}
+
+/// Returns the counters of the current thread
+pub fn snapshot() -> Stats {
+    STATS.with(Cell::get)
+}
+
+/// Reset the counters of the current thread to zero, and returns the old counters
+pub fn reset() -> Stats {
+    STATS.with(|stats| stats.replace(Stats::default()))
+}
+
+pub(crate) fn record(f: impl FnOnce(&mut Stats)) {
+    // the thread local may be destroyed when dropping other thread locals
+    let _ = STATS.try_with(|stats| {
+        let mut value = stats.get();
+        f(&mut value);
+        stats.set(value);
+    });
+}
+
+pub(crate) fn record_allocation() {
+    record(|stats| stats.allocations = stats.allocations.wrapping_add(1));
+}
+
+pub(crate) fn record_conversion(zero_copy: bool) {
+    record(|stats| if zero_copy {
+        stats.zero_copy_conversions = stats.zero_copy_conversions.wrapping_add(1);
+    } else {
+        stats.copying_conversions = stats.copying_conversions.wrapping_add(1);
+    });
+}
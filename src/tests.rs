@@ -1,16 +1,41 @@
-#![allow(unused_imports)]
+#![allow(unused_imports, clippy::unused_unit, clippy::bool_assert_comparison)]
 
 extern crate std;
 
 use core::panic::AssertUnwindSafe;
 use std::panic::catch_unwind;
 
-use alloc::{borrow::ToOwned, boxed::Box, rc::Rc, string::String};
+use alloc::{borrow::ToOwned, boxed::Box, rc::Rc, string::String, sync::Arc};
 
-use crate::rc_vec;
+use crate::{rc_vec, ArcSlice, ArcString, RcSlice, RcString, TryReserveErrorKind};
 
 use super::rc_vec::*;
 
+// the audit of `Send` and `Sync`, only the Arc variants are thread safe
+mod send_sync {
+    use core::cell::Cell;
+    use alloc::rc::Rc;
+    use static_assertions::{assert_impl_all, assert_not_impl_any};
+
+    use crate::*;
+
+    assert_impl_all!(ArcVec<i32>: Send, Sync);
+    assert_impl_all!(ArcVecIntoIter<i32>: Send, Sync);
+    assert_impl_all!(ArcVecDrain<'static, i32>: Send, Sync);
+    assert_impl_all!(CowArcVec<i32>: Send, Sync);
+
+    assert_not_impl_any!(RcVec<i32>: Send, Sync);
+    assert_not_impl_any!(RcVecIntoIter<i32>: Send, Sync);
+    assert_not_impl_any!(RcVecDrain<'static, i32>: Send, Sync);
+    assert_not_impl_any!(CowRcVec<i32>: Send, Sync);
+
+    assert_not_impl_any!(ArcVec<Rc<i32>>: Send, Sync);
+    assert_not_impl_any!(ArcVecIntoIter<Rc<i32>>: Send, Sync);
+    assert_not_impl_any!(ArcVecDrain<'static, Rc<i32>>: Send, Sync);
+    // shared, the elements may be dropped by any owner
+    assert_not_impl_any!(CowArcVec<Cell<i32>>: Send, Sync);
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 struct Zst;
 impl Drop for Zst {
@@ -429,6 +454,43 @@ fn write() {
     assert_eq!(vec, b"hello");
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn write_vectored() {
+    use std::io::{IoSlice, Write};
+    let mut vec = RcVec::new();
+
+    let bufs = [IoSlice::new(b"ab"), IoSlice::new(b""), IoSlice::new(b"cde")];
+    assert_eq!(vec.write_vectored(&bufs).unwrap(), 5);
+    assert_eq!(vec, b"abcde");
+}
+
+#[test]
+fn extend_copy() {
+    let mut vec = rc_vec![1, 2];
+    vec.extend_from_copy_slice(&[3, 4]);
+    vec.extend_from_copy_slice(&[]);
+    assert_eq!(vec, [1, 2, 3, 4]);
+
+    vec.extend(&[5, 6]);
+    vec.extend([7, 8, 9].iter().filter(|&&n| n != 8));
+    assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7, 9]);
+
+    let mut vec: RcVec<()> = RcVec::new();
+    vec.extend_from_copy_slice(&[(); 3]);
+    vec.extend(&[(); 2]);
+    assert_eq!(vec.len(), 5);
+    vec.extend_from_copy_within(1..);
+    assert_eq!(vec.len(), 9);
+
+    let mut vec = RcVec::with_capacity(3);
+    vec.extend_from_copy_slice(b"abc");
+    vec.extend_from_copy_within(..2);
+    vec.extend_from_copy_within(3..);
+    vec.extend_from_copy_within(5..5);
+    assert_eq!(vec, b"abcabab");
+}
+
 #[test]
 fn shrink_to_fit() {
     let mut vec = RcVec::with_capacity(68);
@@ -767,3 +829,1029 @@ fn retain_unwind_catch() {
     assert_eq!(ret.is_err(), true);
     assert_eq!(rcvec, [2, 0, -1, -2]);
 }
+
+#[test]
+fn string_push() {
+    let mut s = RcString::new();
+    s.push_str("foo");
+    s.push('b');
+    s.push('α');
+    assert_eq!(s, "foobα");
+    assert_eq!(s.len(), 6);
+    assert_eq!(s.pop(), Some('α'));
+    assert_eq!(s.pop(), Some('b'));
+    assert_eq!(s, "foo");
+}
+
+#[test]
+fn string_insert_remove() {
+    let mut s = RcString::from("αγ");
+    s.insert(2, 'β');
+    assert_eq!(s, "αβγ");
+    s.insert_str(0, "ab");
+    assert_eq!(s, "abαβγ");
+    assert_eq!(s.remove(2), 'α');
+    assert_eq!(s, "abβγ");
+    s.truncate(4);
+    assert_eq!(s, "abβ");
+}
+
+#[test]
+#[should_panic = "char boundary"]
+fn string_truncate_boundary() {
+    let mut s = RcString::from("αβ");
+    s.truncate(1);
+}
+
+#[test]
+fn string_drain() {
+    let mut s = RcString::from("foo αβ bar");
+    let drained: String = s.drain(4..8).collect();
+    assert_eq!(drained, "αβ");
+    assert_eq!(s, "foo  bar");
+
+    let mut iter = s.drain(..3);
+    assert_eq!(iter.next(), Some('f'));
+    drop(iter);
+    assert_eq!(s, "  bar");
+}
+
+#[test]
+fn string_fmt_write() {
+    use core::fmt::Write;
+
+    let mut s = ArcString::new();
+    let (n, c) = (1, 'a');
+    write!(s, "{n}-{c}").unwrap();
+    assert_eq!(s, "1-a");
+}
+
+#[test]
+fn string_rc_str_roundtrip() {
+    let rc: Rc<str> = Rc::from("foobar");
+    let ptr = Rc::as_ptr(&rc).cast::<u8>();
+
+    let s = RcString::from(rc);
+    assert_eq!(s.as_ptr(), ptr);
+
+    let rc = s.into_rc_str();
+    assert_eq!(Rc::as_ptr(&rc).cast::<u8>(), ptr);
+    assert_eq!(&*rc, "foobar");
+}
+
+#[test]
+fn generic_kind_types() {
+    use crate::{kind, CowVec, RcKind, SharedSlice, SharedString, SharedVec};
+
+    fn build<K: RcKind>(s: &str) -> (SharedString<K>, CowVec<u8, K>) {
+        let string = SharedString::<K>::from(s);
+        let cow = string.clone().into_bytes().freeze();
+        (string, cow)
+    }
+
+    fn roundtrip<K: RcKind>(string: SharedString<K>) -> SharedString<K> {
+        SharedString::from_rc_str(string.into_rc_str())
+    }
+
+    let (s, cow) = build::<kind::Rc>("rc");
+    assert_eq!(roundtrip(s), "rc");
+    assert_eq!(cow, *b"rc");
+
+    let (s, mut cow) = build::<kind::Arc>("arc");
+    let ptr = s.as_ptr();
+    let s = roundtrip(s);
+    assert_eq!(s.as_ptr(), ptr);
+    cow.make_mut().push(b'!');
+    assert_eq!(cow, *b"arc!");
+
+    let rc: Arc<[u8; 3]> = cow.thaw().split_off(1).try_into_rc_array().unwrap();
+    assert_eq!(*rc, *b"rc!");
+
+    fn tail<K: RcKind>(vec: SharedVec<u8, K>) -> SharedVec<u8, K> {
+        let slice = SharedSlice::from(vec);
+        let tail = slice.slice(1..);
+        drop(slice);
+        tail.into_rc_vec()
+    }
+    assert_eq!(tail(ArcVec::from(b"arc".as_slice())), b"rc");
+    assert_eq!(tail(RcVec::from(b"rc".as_slice())), b"c");
+}
+
+#[test]
+fn string_from_utf8() {
+    let s = RcString::from_utf8(rc_vec![b'o', b'k']).unwrap();
+    assert_eq!(s, "ok");
+
+    let err = RcString::from_utf8(rc_vec![b'o', 0xff]).unwrap_err();
+    assert_eq!(err.utf8_error().valid_up_to(), 1);
+    assert_eq!(err.into_bytes(), [b'o', 0xff]);
+}
+
+#[test]
+fn try_reserve() {
+    let mut vec = rc_vec!["a".to_owned()];
+    vec.try_reserve(3).unwrap();
+    assert!(vec.capacity() >= 4);
+    vec.try_reserve_exact(7).unwrap();
+    assert_eq!(vec.capacity(), 8);
+
+    let err = vec.try_reserve(usize::MAX).unwrap_err();
+    assert_eq!(err.kind(), TryReserveErrorKind::CapacityOverflow);
+    let err = vec.try_reserve_exact(isize::MAX as usize).unwrap_err();
+    assert_eq!(err.kind(), TryReserveErrorKind::CapacityOverflow);
+    assert_eq!(vec, ["a".to_owned()]);
+}
+
+#[test]
+fn try_push() {
+    let mut vec = RcVec::new();
+    vec.try_push("a".to_owned()).unwrap();
+    vec.try_extend_from_slice(&["b".to_owned(), "c".to_owned()]).unwrap();
+    assert_eq!(vec, ["a".to_owned(), "b".to_owned(), "c".to_owned()]);
+
+    let mut vec = RcVec::new();
+    unsafe { vec.set_len(usize::MAX) }
+    let err = vec.try_push(()).unwrap_err();
+    assert_eq!(err.reserve_error().kind(), TryReserveErrorKind::CapacityOverflow);
+    unsafe { vec.set_len(0) }
+
+    let mut vec: RcVec<Zst> = RcVec::new();
+    unsafe { vec.set_len(usize::MAX) }
+    let err = vec.try_push(Zst).unwrap_err();
+    assert_eq!(err.into_value(), Zst);
+    unsafe { vec.set_len(0) }
+}
+
+#[test]
+fn try_from_rc() {
+    #[derive(Debug)]
+    struct NoClone(i32);
+
+    let rc: Rc<[NoClone]> = Rc::new([NoClone(1), NoClone(2)]);
+    let ptr = Rc::as_ptr(&rc).cast::<NoClone>();
+    let weak = Rc::downgrade(&rc);
+
+    let rc = RcVec::try_from_rc(rc).unwrap_err();
+    drop(weak);
+
+    let vec = RcVec::try_from_rc(rc).ok().unwrap();
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec.len(), 2);
+    assert_eq!(vec[1].0, 2);
+}
+
+#[test]
+fn from_rc_or_clone() {
+    let rc: Rc<[String]> = Rc::new(["a".to_owned(), "b".to_owned()]);
+    let ptr = Rc::as_ptr(&rc).cast::<String>();
+
+    let (vec, reused) = RcVec::from_rc_or_clone(rc.clone());
+    assert!(!reused);
+    assert_ne!(vec.as_ptr(), ptr);
+
+    let (vec, reused) = RcVec::from_rc_or_clone(rc);
+    assert!(reused);
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec, ["a".to_owned(), "b".to_owned()]);
+}
+
+#[test]
+fn shared_clone() {
+    let a = rc_vec!["a".to_owned(), "b".to_owned()].into_shared();
+    let b = a.clone();
+    assert_eq!(a.as_ptr(), b.as_ptr());
+    assert!(!a.is_unique());
+
+    let vec = b.try_into_rc_vec().unwrap_err();
+    drop(vec);
+    assert!(a.is_unique());
+
+    let vec = a.try_into_rc_vec().unwrap();
+    assert_eq!(vec, ["a".to_owned(), "b".to_owned()]);
+
+    // the buffer of the vector is shared
+    let ptr = vec.as_ptr();
+    let a = vec.into_shared();
+    let b = a.clone();
+    assert_eq!(b.as_ptr(), ptr);
+    drop(a);
+    assert_eq!(b.try_into_rc_vec().unwrap().as_ptr(), ptr);
+}
+
+#[test]
+fn shared_make_mut() {
+    let mut vec = RcVec::with_capacity(4);
+    vec.push("a".to_owned());
+    let ptr = vec.as_ptr();
+
+    let mut a = CowRcVec::from(vec);
+    assert_eq!(a.capacity(), 4);
+    a.make_mut().push("b".to_owned());
+    assert_eq!(a.as_ptr(), ptr);
+
+    let b = a.clone();
+    a.make_mut().push("c".to_owned());
+    assert_ne!(a.as_ptr(), ptr);
+    assert_eq!(b.as_ptr(), ptr);
+    assert_eq!(a, ["a".to_owned(), "b".to_owned(), "c".to_owned()]);
+    assert_eq!(b, ["a".to_owned(), "b".to_owned()]);
+}
+
+#[test]
+fn shared_zst() {
+    let a = rc_vec![Zst, Zst].into_shared();
+    let b = a.clone();
+    assert_eq!(b.len(), 2);
+    drop(a);
+    assert_eq!(b.try_into_rc_vec().unwrap(), [Zst, Zst]);
+
+    let empty = CowArcVec::<Zst>::new();
+    assert_eq!(empty.clone().len(), 0);
+}
+
+#[test]
+fn shared_arc_concurrent_drop() {
+    use std::sync::{atomic::{AtomicUsize, Ordering}, Barrier};
+
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted(#[allow(unused)] u8);
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let rounds = if cfg!(miri) { 8 } else { 2000 };
+    for _ in 0..rounds {
+        let a = CowArcVec::from(ArcVec::from([Counted(1), Counted(2), Counted(3)]));
+        let owners = [a.clone(), a.clone(), a.clone(), a];
+        let barrier = Barrier::new(owners.len());
+
+        std::thread::scope(|s| {
+            for vec in owners {
+                let barrier = &barrier;
+                s.spawn(move || {
+                    barrier.wait();
+                    drop(vec);
+                });
+            }
+        });
+    }
+    assert_eq!(DROPS.load(Ordering::Relaxed), rounds * 3);
+}
+
+#[test]
+fn frozen_arc_concurrent_drop() {
+    use std::sync::{atomic::{AtomicUsize, Ordering}, Barrier};
+
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted(#[allow(unused)] u8);
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let rounds = if cfg!(miri) { 8 } else { 2000 };
+    let mut thawed = 0;
+    for _ in 0..rounds {
+        let mut vec = ArcVec::with_capacity(8);
+        vec.extend([Counted(1), Counted(2), Counted(3)]);
+        let frozen: FrozenArcVec<_> = vec.freeze();
+        let owners = [frozen.clone(), frozen.clone(), frozen.clone(), frozen];
+        let barrier = Barrier::new(owners.len());
+
+        thawed += std::thread::scope(|s| {
+            let handles = owners.map(|frozen| {
+                let barrier = &barrier;
+                s.spawn(move || {
+                    barrier.wait();
+                    // only the last owner can thaw without copy
+                    let Ok(vec) = frozen.try_into_rc_vec() else { return 0 };
+                    assert_eq!(vec.capacity(), 8);
+                    assert_eq!(vec.len(), 3);
+                    1
+                })
+            });
+            handles.map(|handle| handle.join().unwrap()).iter().sum::<usize>()
+        });
+    }
+    assert!(thawed <= rounds);
+    assert_eq!(DROPS.load(Ordering::Relaxed), rounds * 3);
+}
+
+#[test]
+fn dedup() {
+    let mut vec = rc_vec!["a".to_owned(), "a".to_owned(), "b".to_owned(), "a".to_owned(), "a".to_owned()];
+    vec.dedup();
+    assert_eq!(vec, ["a".to_owned(), "b".to_owned(), "a".to_owned()]);
+
+    let mut vec = rc_vec![Zst, Zst, Zst];
+    vec.dedup();
+    assert_eq!(vec, [Zst]);
+}
+
+#[test]
+fn dedup_unwind_catch() {
+    let mut vec = rc_vec![1, 1, 2, 2, 0, 3, 3];
+    let ret = catch_unwind(AssertUnwindSafe(|| {
+        vec.dedup_by(|a, b| {
+            assert_ne!(*a, 0, "Boom by zero");
+            a == b
+        });
+    }));
+    assert!(ret.is_err());
+    assert_eq!(vec, [1, 2, 0, 3, 3]);
+}
+
+#[test]
+fn splice() {
+    let mut vec = rc_vec!["a".to_owned(), "b".to_owned(), "c".to_owned()];
+    let removed: RcVec<_> = vec.splice(1..2, ["d".to_owned(), "e".to_owned()]).collect();
+    assert_eq!(removed, ["b".to_owned()]);
+    assert_eq!(vec, ["a".to_owned(), "d".to_owned(), "e".to_owned(), "c".to_owned()]);
+
+    // unknown size_hint
+    vec.splice(..1, ["f", "g", "h"].iter().map(|&s| s.to_owned()).filter(|_| true));
+    assert_eq!(*vec, ["f", "g", "h", "d", "e", "c"]);
+
+    vec.splice(2.., []);
+    assert_eq!(*vec, ["f", "g"]);
+
+    let mut vec = rc_vec![Zst, Zst];
+    vec.splice(1..1, [Zst, Zst]);
+    assert_eq!(vec.len(), 4);
+}
+
+#[test]
+fn insert_many() {
+    let mut vec = rc_vec!["a".to_owned(), "b".to_owned()];
+    vec.insert_many(1, ["c".to_owned(), "d".to_owned()]);
+    assert_eq!(*vec, ["a", "c", "d", "b"]);
+    vec.insert_many(4, ["e".to_owned()]);
+    assert_eq!(*vec, ["a", "c", "d", "b", "e"]);
+}
+
+#[test]
+#[should_panic = "insertion index (is 3) should be <= len (is 2)"]
+fn insert_many_out_of_bounds() {
+    let mut vec = rc_vec![1, 2];
+    vec.insert_many(3, [1]);
+}
+
+#[test]
+fn extract_if() {
+    let mut vec = rc_vec!["a".to_owned(), "bb".to_owned(), "c".to_owned(), "dd".to_owned(), "e".to_owned()];
+    let mut iter = vec.extract_if(1.., |s| s.len() == 2);
+    assert_eq!(iter.next(), Some("bb".to_owned()));
+    drop(iter);
+    assert_eq!(*vec, ["a", "c", "dd", "e"]);
+
+    let extracted: RcVec<_> = vec.extract_if(.., |s| s.len() == 2).collect();
+    assert_eq!(*extracted, ["dd"]);
+    assert_eq!(*vec, ["a", "c", "e"]);
+}
+
+#[test]
+fn generic_kind() {
+    use crate::{arc_vec, RcKind};
+
+    fn push_sum<K: RcKind>(mut vec: SharedVec<u32, K>) -> (SharedVec<u32, K>, u32) {
+        vec.push(vec.iter().sum());
+        let sum = vec.drain(..).sum();
+        (vec, sum)
+    }
+
+    let (vec, sum) = push_sum(rc_vec![1, 2, 3]);
+    assert_eq!(sum, 12);
+    assert!(vec.is_empty());
+
+    let (vec, sum) = push_sum(arc_vec![4, 5]);
+    assert_eq!(sum, 18);
+    let rc: alloc::sync::Arc<[u32]> = vec.into_rc_slice();
+    assert_eq!(*rc, []);
+}
+
+#[test]
+fn zst_into_rc_slice() {
+    let vec: RcVec<()> = rc_vec![(); 3];
+    let rc = vec.into_rc_slice();
+    assert_eq!(rc.len(), 3);
+
+    let vec: RcVec<()> = RcVec::new();
+    assert_eq!(vec.into_rc_slice().len(), 0);
+}
+
+#[cfg(feature = "allocator_api2")]
+#[test]
+fn custom_allocator() {
+    use allocator_api2::alloc::{AllocError, Allocator, Global};
+    use core::{alloc::Layout, cell::Cell, ptr::NonNull};
+
+    #[derive(Default)]
+    struct Counter {
+        bytes: Cell<usize>,
+    }
+
+    unsafe impl Allocator for &Counter {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.bytes.set(self.bytes.get() + layout.size());
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.bytes.set(self.bytes.get() - layout.size());
+            Global.deallocate(ptr, layout);
+        }
+    }
+
+    let counter = Counter::default();
+    let mut vec = RcVec::new_in(&counter);
+    vec.extend(["a".to_owned(), "b".to_owned()]);
+    assert_ne!(counter.bytes.get(), 0);
+
+    let mut other = vec.split_off(1);
+    other.push("c".to_owned());
+    other.shrink_to_fit();
+    assert_eq!(*vec, ["a"]);
+    assert_eq!(*other, ["b", "c"]);
+
+    let mut iter = other.into_iter();
+    assert_eq!(iter.next().as_deref(), Some("b"));
+    drop(iter);
+    drop(vec);
+    assert_eq!(counter.bytes.get(), 0);
+
+    let vec = ArcVec::<u8, _>::try_with_capacity_in(4, &counter).unwrap();
+    assert_eq!(vec.capacity(), 4);
+    drop(vec);
+    assert_eq!(counter.bytes.get(), 0);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_seq() {
+    use serde_test::{assert_de_tokens, assert_tokens, Token};
+
+    let vec: RcVec<i32> = rc_vec![1, 2, 3];
+    assert_tokens(&vec, &[
+        Token::Seq { len: Some(3) },
+        Token::I32(1),
+        Token::I32(2),
+        Token::I32(3),
+        Token::SeqEnd,
+    ]);
+
+    let vec: ArcVec<String> = crate::arc_vec!["a".to_owned()];
+    assert_de_tokens(&vec, &[
+        Token::Seq { len: None },
+        Token::Str("a"),
+        Token::SeqEnd,
+    ]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_as_bytes() {
+    use serde::{Deserialize, Serialize};
+    use serde_test::{assert_de_tokens, assert_tokens, Token};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Bytes(#[serde(with = "crate::serde_as_bytes")] ArcVec<u8>);
+
+    let bytes = Bytes(ArcVec::from(b"abc".as_slice()));
+    assert_tokens(&bytes, &[Token::Bytes(b"abc")]);
+    assert_de_tokens(&bytes, &[Token::ByteBuf(b"abc")]);
+    assert_de_tokens(&bytes, &[Token::Str("abc")]);
+    assert_de_tokens(&bytes, &[
+        Token::Seq { len: Some(3) },
+        Token::U8(b'a'),
+        Token::U8(b'b'),
+        Token::U8(b'c'),
+        Token::SeqEnd,
+    ]);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn bytes_buf() {
+    use bytes::{Buf, BufMut, Bytes};
+
+    let mut vec = RcVec::new();
+    vec.put_u8(1);
+    vec.put_u16(0x0203);
+    vec.put_slice(b"abc");
+    vec.put(&b"de"[..]);
+    assert_eq!(vec, [1, 2, 3, b'a', b'b', b'c', b'd', b'e']);
+    assert!(vec.chunk_mut().len() > 0);
+
+    let mut iter = vec.into_iter();
+    assert_eq!(iter.remaining(), 8);
+    assert_eq!(iter.get_u8(), 1);
+    assert_eq!(iter.get_u16(), 0x0203);
+    assert_eq!(iter.chunk(), b"abcde");
+    iter.advance(2);
+    assert_eq!(iter.copy_to_bytes(2), Bytes::from_static(b"cd"));
+    assert_eq!(iter.next(), Some(b'e'));
+    assert!(!iter.has_remaining());
+
+    let vec = ArcVec::from(b"abc".as_slice());
+    let ptr = vec.as_ptr();
+    let bytes = Bytes::from(vec);
+    assert_eq!(bytes, b"abc".as_slice());
+    assert_eq!(bytes.as_ptr(), ptr);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn rayon_par_iter() {
+    use rayon::prelude::*;
+    use std::{string::ToString, vec::Vec};
+
+    let vec: ArcVec<String> = (0..1000).into_par_iter()
+        .map(|i| i.to_string())
+        .collect();
+    assert_eq!(vec.len(), 1000);
+    assert!(vec.iter().enumerate().all(|(i, s)| *s == i.to_string()));
+
+    let sum: usize = vec.clone().into_par_iter()
+        .map(|s| s.parse::<usize>().unwrap())
+        .sum();
+    assert_eq!(sum, 999 * 1000 / 2);
+
+    let mut vec = vec;
+    let drained: Vec<String> = vec.par_drain(10..990).collect();
+    assert_eq!(drained.len(), 980);
+    assert_eq!(drained[0], "10");
+    assert_eq!(vec.len(), 20);
+    assert_eq!(vec[9], "9");
+    assert_eq!(vec[10], "990");
+
+    drop(vec.par_drain(..5));
+    assert_eq!(vec.len(), 15);
+    assert_eq!(vec[0], "5");
+
+    vec.par_extend(["a".to_owned(), "b".to_owned()]);
+    assert_eq!(vec.len(), 17);
+    assert_eq!(vec[16], "b");
+
+    let mut vec = crate::arc_vec![1, 2];
+    vec.par_extend(&[3, 4]);
+    assert_eq!(vec, [1, 2, 3, 4]);
+
+    let vec: ArcVec<()> = (0..100).into_par_iter().map(|_| ()).collect();
+    assert_eq!(vec.into_par_iter().count(), 100);
+}
+
+#[test]
+fn send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<ArcVec<String>>();
+    assert_send_sync::<ArcVecDrain<'_, String>>();
+    assert_send_sync::<ArcVecIntoIter<String>>();
+    assert_send_sync::<ArcVecDeque<String>>();
+}
+
+#[test]
+fn zst_spare_capacity() {
+    let mut vec: RcVec<()> = rc_vec![(), ()];
+    assert_eq!(vec.spare_capacity_mut().len(), usize::MAX - 2);
+
+    vec.extend_from_within(..);
+    assert_eq!(vec.len(), 4);
+}
+
+#[test]
+fn resize_shrink() {
+    let mut vec = rc_vec![1, 2, 3, 4];
+    vec.resize(2, 0);
+    assert_eq!(vec, [1, 2]);
+    vec.resize_with(1, || 0);
+    assert_eq!(vec, [1]);
+    vec.resize(3, 5);
+    assert_eq!(vec, [1, 5, 5]);
+}
+
+#[test]
+fn map_in_place() {
+    let vec = rc_vec![1u64, 2, 3, 4];
+    let ptr = vec.as_ptr();
+    let vec: RcVec<i64> = vec.into_iter().map_in_place(|n| n as i64 * 2);
+    assert_eq!(vec, [2, 4, 6, 8]);
+    assert_eq!(vec.as_ptr().cast(), ptr);
+
+    let mut iter = vec.into_iter();
+    iter.next();
+    iter.next_back();
+    let vec: RcVec<u64> = iter.filter_map_in_place(|n| (n != 4).then_some(n as u64));
+    assert_eq!(vec, [6]);
+    assert_eq!(vec.as_ptr().cast(), ptr);
+
+    let vec: RcVec<u8> = rc_vec![1u32, 2].into_iter().map_in_place(|n| n as u8);
+    assert_eq!(vec, [1, 2]);
+
+    let vec: RcVec<()> = rc_vec![Zst, Zst].into_iter().map_in_place(drop);
+    assert_eq!(vec.len(), 2);
+
+    let vec = rc_vec![String::from("a"), String::from("b"), String::from("c")];
+    let ret = catch_unwind(AssertUnwindSafe(|| {
+        vec.into_iter().map_in_place(|s| {
+            assert_ne!(s, "b", "Boom by b");
+            s + "!"
+        })
+    }));
+    assert_eq!(ret.is_err(), true);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn cursor() {
+    use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+
+    let mut cursor = RcVecCursor::new(RcVec::from(b"abc\ndef".as_slice()));
+    let mut line = String::new();
+    cursor.read_line(&mut line).unwrap();
+    assert_eq!(line, "abc\n");
+    assert_eq!(cursor.position(), 4);
+
+    cursor.write_all(b"DEFGH").unwrap();
+    assert_eq!(cursor.get_ref(), b"abc\nDEFGH");
+    assert!(cursor.is_empty());
+
+    cursor.seek(SeekFrom::End(2)).unwrap();
+    cursor.write_all(b"!").unwrap();
+    assert_eq!(cursor.get_ref(), b"abc\nDEFGH\0\0!");
+
+    assert!(cursor.seek(SeekFrom::Current(-100)).is_err());
+    cursor.seek(SeekFrom::Start(1)).unwrap();
+    let mut buf = [0; 2];
+    cursor.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"bc");
+    assert_eq!(cursor.remaining_slice(), b"\nDEFGH\0\0!");
+
+    cursor.set_position(100);
+    assert_eq!(cursor.read(&mut buf).unwrap(), 0);
+
+    let cursor = ArcVecCursor::from(ArcVec::from(b"xyz".as_slice()));
+    let rc = cursor.into_rc_slice();
+    assert_eq!(*rc, *b"xyz");
+}
+
+#[test]
+fn fmt_write() {
+    use core::fmt::Write;
+
+    let mut vec: RcVec<u8> = RcVec::new();
+    write!(vec, "a-{:02}", 7).unwrap();
+    assert_eq!(vec, b"a-07");
+
+    let mut vec: ArcVec<u8> = ArcVec::from(b"x".as_slice());
+    vec.write_str("yz").unwrap();
+    assert_eq!(vec, b"xyz");
+}
+
+#[cfg(feature = "embedded_io")]
+#[test]
+fn embedded_io() {
+    use embedded_io::{BufRead, Read, Seek, SeekFrom, Write};
+
+    let mut vec: RcVec<u8> = RcVec::new();
+    vec.write_all(b"hello ").unwrap();
+    write!(vec, "{}", 42).unwrap();
+    assert_eq!(vec, b"hello 42");
+
+    let mut iter = vec.into_iter();
+    let mut buf = [0; 5];
+    assert_eq!(iter.read(&mut buf).unwrap(), 5);
+    assert_eq!(&buf, b"hello");
+    assert_eq!(iter.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b" 42");
+    assert_eq!(iter.read(&mut buf).unwrap(), 0);
+
+    let mut cursor = ArcVecCursor::new(ArcVec::from(b"abcdef".as_slice()));
+    cursor.read_exact(&mut buf[..2]).unwrap();
+    assert_eq!(&buf[..2], b"ab");
+    assert_eq!(cursor.fill_buf().unwrap(), b"cdef");
+    cursor.consume(1);
+    cursor.write_all(b"DEFG").unwrap();
+    assert_eq!(cursor.get_ref(), b"abcDEFG");
+
+    cursor.seek(SeekFrom::End(1)).unwrap();
+    cursor.write_all(b"!").unwrap();
+    assert_eq!(cursor.get_ref(), b"abcDEFG\0!");
+    assert!(cursor.seek(SeekFrom::Current(-100)).is_err());
+}
+
+#[test]
+fn vec_deque() {
+    let mut deque = RcVecDeque::with_capacity(4);
+    deque.push_back("b".to_owned());
+    deque.push_back("c".to_owned());
+    deque.push_front("a".to_owned());
+    deque.push_front("z".to_owned());
+    assert_eq!(deque.capacity(), 4);
+    assert_eq!(deque.as_slices().1, ["b", "c"]);
+
+    // grow while wrapped
+    deque.push_back("d".to_owned());
+    assert!(deque.iter().eq(["z", "a", "b", "c", "d"]));
+    deque.push_front("y".to_owned());
+    assert_eq!(deque.len(), 6);
+    assert_eq!(deque[0], "y");
+    assert_eq!(deque.back().unwrap(), "d");
+
+    assert_eq!(deque.pop_front().unwrap(), "y");
+    assert_eq!(deque.pop_back().unwrap(), "d");
+    assert!(deque.iter().eq(["z", "a", "b", "c"]));
+    assert_eq!(deque.clone(), deque);
+
+    let rc = deque.into_rc_slice();
+    assert_eq!(*rc, ["z", "a", "b", "c"]);
+
+    let mut deque = RcVecDeque::with_capacity(4);
+    deque.extend([1, 2, 3]);
+    deque.pop_front();
+    deque.extend([4, 5, 6]);
+    assert!(deque.iter().eq(&[2, 3, 4, 5, 6]));
+    assert_eq!(deque.as_slices().1, []);
+
+    let mut deque = ArcVecDeque::from(ArcVec::from([1, 2, 3]));
+    deque.push_front(0);
+    deque.pop_back();
+    assert_eq!(deque.make_contiguous(), [0, 1, 2]);
+    let vec = ArcVec::from(deque);
+    assert_eq!(vec, [0, 1, 2]);
+
+    let mut deque: RcVecDeque<()> = RcVecDeque::new();
+    deque.push_front(());
+    deque.push_front(());
+    deque.push_back(());
+    assert_eq!(deque.len(), 3);
+    assert_eq!(deque.make_contiguous().len(), 3);
+    assert_eq!(deque.into_rc_slice().len(), 3);
+}
+
+#[test]
+fn vec_deque_into_rc_slice_in_place() {
+    let mut deque: RcVecDeque<u32> = (0..6).collect();
+    let start = deque.as_slices().0.as_ptr();
+    deque.pop_front();
+    deque.pop_front();
+    deque.push_back(6);
+    deque.push_back(7);
+    assert_eq!(deque.as_slices(), (&[2, 3, 4, 5][..], &[6, 7][..]));
+
+    let rc = deque.into_rc_slice();
+    assert_eq!(*rc, [2, 3, 4, 5, 6, 7]);
+    assert_eq!(rc.as_ptr(), start);
+}
+
+#[test]
+fn small_vec() {
+    let mut vec: SmallRcVec<String, 2> = SmallRcVec::new();
+    vec.push("a".to_owned());
+    vec.push("b".to_owned());
+    assert!(!vec.spilled());
+    assert_eq!(vec.capacity(), 2);
+    assert_eq!(vec.pop().unwrap(), "b");
+    vec.push("b".to_owned());
+    assert_eq!(vec.clone(), vec);
+
+    vec.push("c".to_owned());
+    assert!(vec.spilled());
+    assert_eq!(*vec, ["a", "b", "c"]);
+    vec.truncate(1);
+    vec.extend(["d".to_owned(), "e".to_owned()]);
+    assert_eq!(*vec, ["a", "d", "e"]);
+
+    let rc = vec.into_rc_slice();
+    assert_eq!(*rc, ["a", "d", "e"]);
+
+    let mut vec: SmallArcVec<String, 4> = ["x", "y"].map(ToOwned::to_owned).into_iter().collect();
+    assert!(!vec.spilled());
+    vec.truncate(1);
+    let rc = vec.into_rc_slice();
+    assert_eq!(*rc, ["x"]);
+
+    let mut vec: SmallRcVec<u8, 4> = SmallRcVec::with_capacity(2);
+    vec.reserve(3);
+    assert!(!vec.spilled());
+    vec.reserve(5);
+    assert!(vec.spilled());
+    assert!(vec.capacity() >= 5);
+
+    let vec: SmallRcVec<(), 4> = core::iter::repeat_n((), 9).collect();
+    assert!(vec.spilled());
+    assert_eq!(vec.len(), 9);
+    assert_eq!(RcVec::from(vec).len(), 9);
+
+    let vec: SmallRcVec<u8, 4> = SmallRcVec::new();
+    assert_eq!(*vec.into_rc_slice(), []);
+}
+
+#[test]
+fn rc_slice() {
+    let all = RcSlice::from(rc_vec!["a", "b", "c", "d"]);
+    let tail = all.slice(1..);
+    assert_eq!(tail, ["b", "c", "d"]);
+    assert_eq!(tail.slice(..=1), ["b", "c"]);
+    assert_eq!(tail.slice(3..), []);
+
+    let (a, b) = tail.split_at(2);
+    assert_eq!(a, ["b", "c"]);
+    assert_eq!(b, ["d"]);
+    assert_eq!(b.as_ptr(), all[3..].as_ptr());
+    assert!(Rc::ptr_eq(a.as_rc(), all.as_rc()));
+
+    let whole = tail.slice(..).clone();
+    let whole = whole.try_into_rc_vec().unwrap_err();
+    assert_eq!(whole.into_rc_vec(), ["b", "c", "d"]);
+
+    let all = all.try_into_rc_vec().unwrap_err();
+    drop((tail, a, b));
+    let mut vec = all.try_into_rc_vec().unwrap();
+    vec.push("e");
+    assert_eq!(vec, ["a", "b", "c", "d", "e"]);
+
+    let arc: ArcSlice<u8> = ArcSlice::from(alloc::sync::Arc::from(*b"xyz"));
+    let (x, yz) = arc.split_at(1);
+    assert_eq!(x, b"x".as_slice());
+    assert_eq!(yz, *b"yz");
+    assert_eq!(RcSlice::<u8>::default(), []);
+}
+
+#[test]
+#[should_panic = "range end index 4 out of range for slice of length 3"]
+fn rc_slice_out_of_bounds() {
+    let all = RcSlice::from(rc_vec![1, 2, 3, 4]);
+    let _ = all.slice(1..).slice(..4);
+}
+
+#[test]
+fn freeze_thaw() {
+    let mut vec: RcVec<String> = RcVec::with_capacity(16);
+    vec.push("a".to_owned());
+    let ptr = vec.as_ptr();
+
+    for i in 0..3 {
+        let frozen: FrozenRcVec<String> = vec.freeze();
+        assert_eq!(frozen.len(), i + 1);
+        assert_eq!(frozen.capacity(), 16);
+
+        let reader = frozen.clone();
+        let copy = reader.thaw();
+        assert_ne!(copy.as_ptr(), ptr);
+
+        vec = frozen.thaw();
+        assert_eq!(vec.as_ptr(), ptr);
+        assert_eq!(vec.capacity(), 16);
+        assert_eq!(vec, copy);
+        vec.push(alloc::format!("{i}"));
+    }
+    assert_eq!(*vec, ["a", "0", "1", "2"]);
+
+    let frozen: FrozenArcVec<u8> = ArcVec::new().freeze();
+    assert_eq!(frozen.thaw().capacity(), 0);
+}
+
+#[test]
+#[cfg(feature = "stats")]
+fn freeze_thaw_no_alloc() {
+    use crate::stats;
+
+    let mut vec: ArcVec<String> = ArcVec::with_capacity(4);
+    vec.push("a".to_owned());
+    let ptr = vec.as_ptr();
+
+    stats::reset();
+    let frozen = vec.freeze();
+    let shared = frozen.clone();
+    assert_eq!(shared.as_ptr(), ptr);
+    drop(shared);
+    let vec = frozen.thaw();
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec.capacity(), 4);
+
+    let s = stats::snapshot();
+    assert_eq!(s.allocations, 0);
+    assert_eq!(s.reallocations, 0);
+    assert_eq!(s.copying_conversions, 0);
+}
+
+#[test]
+fn growth_policy() {
+    use crate::{
+        allocator::Global,
+        growth::{FixedIncrement, OneAndHalf, PageRounded},
+    };
+
+    let mut vec: RcVec<u8> = RcVec::new();
+    vec.push(0);
+    assert_eq!(vec.capacity(), 8);
+    vec.extend([0; 8]);
+    assert_eq!(vec.capacity(), 16);
+
+    let mut vec: RcVec<u32, Global, OneAndHalf> = RcVec::with_capacity(4)
+        .with_growth_policy();
+    vec.extend([0; 5]);
+    assert_eq!(vec.capacity(), 6);
+    vec.insert(0, 1);
+    vec.insert(0, 2);
+    assert_eq!(vec.capacity(), 9);
+
+    let vec: ArcVec<u32> = ArcVec::with_capacity(3);
+    let ptr = vec.as_ptr();
+    let mut vec: ArcVec<u32, Global, FixedIncrement<10>> = vec.with_growth_policy();
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec.capacity(), 3);
+    vec.extend([1, 2, 3, 4]);
+    assert_eq!(vec.capacity(), 13);
+    vec.reserve(15);
+    assert_eq!(vec.capacity(), 23);
+    vec.reserve_exact(20);
+    assert_eq!(vec.capacity(), 24);
+    vec.resize(24, 0);
+    vec.insert(0, 0);
+    assert_eq!(vec.capacity(), 34);
+
+    let mut vec: RcVec<u32, Global, OneAndHalf> = (0..5).collect();
+    assert_eq!(vec, [0, 1, 2, 3, 4]);
+    vec.push(5);
+    assert_eq!(vec.capacity(), 7);
+    let vec: ArcVec<u8, Global, FixedIncrement<3>> = Default::default();
+    assert_eq!(vec.capacity(), 0);
+
+    let mut vec: RcVec<u64, Global, PageRounded> = RcVec::default();
+    vec.push(0);
+    assert_eq!(vec.capacity(), (PageRounded::PAGE_SIZE - 16) / 8);
+    vec.resize(vec.capacity() + 1, 1);
+    assert_eq!((vec.capacity() * 8 + 16) % PageRounded::PAGE_SIZE, 0);
+    assert_eq!(vec.pop(), Some(1));
+
+    let mut vec: RcVec<(), Global, FixedIncrement<10>> = RcVec::new().with_growth_policy();
+    vec.extend([(); 100]);
+    assert_eq!(vec.capacity(), usize::MAX);
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn stats() {
+    use crate::stats::{self, Stats};
+
+    stats::reset();
+    let mut vec: RcVec<u64> = RcVec::with_capacity(4);
+    vec.extend([1, 2, 3, 4]);
+    let ptr = vec.as_ptr();
+    vec.push(5);
+    let moved = vec.as_ptr() != ptr;
+
+    let old = stats::reset();
+    assert_eq!(old.allocations, 1);
+    assert_eq!(old.reallocations, 1);
+    // the 4 initialized elements
+    assert_eq!(old.copied_bytes, if moved { 4 * 8 } else { 0 });
+    assert_eq!(stats::snapshot(), Stats::default());
+
+    let rc = vec.into_rc_slice();
+    let shared = rc.clone();
+    let vec = RcVec::from(rc);
+    let vec2 = RcVec::from(shared);
+    let _: Box<[u64]> = vec.into();
+
+    let s = stats::snapshot();
+    // the clone of `shared` and the `Box<[u64]>`
+    assert_eq!(s.allocations, 2);
+    assert_eq!(s.reallocations, 1);
+    assert_eq!(s.zero_copy_conversions, 2);
+    assert_eq!(s.copying_conversions, 2);
+
+    stats::reset();
+    let slice = RcSlice::from(vec2);
+    let part = slice.slice(1..);
+    assert_eq!(part.clone().into_rc_vec(), [2, 3, 4, 5]);
+    drop(part);
+    assert_eq!(slice.into_rc_vec(), [1, 2, 3, 4, 5]);
+
+    let mut a = CowRcVec::from(rc_vec![1]);
+    let b = a.clone();
+    a.make_mut().push(2);
+    drop(b);
+    a.make_mut().push(3);
+    assert_eq!(a, [1, 2, 3]);
+
+    let s = stats::snapshot();
+    // the freeze and thaw of `slice`, and the unique `make_mut`
+    assert_eq!(s.zero_copy_conversions, 3);
+    assert_eq!(s.copying_conversions, 2);
+
+    stats::reset();
+    let _: Box<[u64]> = RcVec::new().into();
+    let _: Box<[()]> = rc_vec![(); 3].into();
+    let s = stats::snapshot();
+    assert_eq!(s.allocations, 0);
+    assert_eq!(s.copying_conversions, 2);
+}
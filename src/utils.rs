@@ -39,3 +39,15 @@ where R: RangeBounds<usize>,
 
     Range { start, end }
 }
+
+/// Limit the preallocation from an untrusted `size_hint`, like serde
+#[cfg(feature = "serde")]
+pub fn cautious_capacity<T>(hint: Option<usize>) -> usize {
+    const MAX_PREALLOC_BYTES: usize = 1024 * 1024;
+
+    if size_of::<T>() == 0 {
+        0
+    } else {
+        hint.unwrap_or(0).min(MAX_PREALLOC_BYTES / size_of::<T>())
+    }
+}
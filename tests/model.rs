@@ -0,0 +1,326 @@
+//! Model based tests, apply random operation sequences to both [`RcVec`] and [`Vec`],
+//! [`RcVecDeque`] and [`VecDeque`]
+
+use std::{cell::Cell, collections::VecDeque, fmt::Debug, rc::Rc};
+
+use proptest::{collection::vec, prelude::*};
+use rc_vec::{RcVec, RcVecDeque};
+
+#[derive(Debug, Clone)]
+enum Op {
+    Push(u8),
+    Pop,
+    Insert(usize, u8),
+    Remove(usize),
+    SwapRemove(usize),
+    Drain(usize, usize),
+    Retain(u8),
+    SplitOff(usize),
+    Append(Vec<u8>),
+    ExtendFromWithin(usize, usize),
+    Truncate(usize),
+    Reserve(usize),
+    ShrinkTo(usize),
+    ShrinkToFit,
+    Clear,
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        4 => any::<u8>().prop_map(Op::Push),
+        2 => Just(Op::Pop),
+        2 => (any::<usize>(), any::<u8>()).prop_map(|(i, v)| Op::Insert(i, v)),
+        2 => any::<usize>().prop_map(Op::Remove),
+        1 => any::<usize>().prop_map(Op::SwapRemove),
+        1 => (any::<usize>(), any::<usize>()).prop_map(|(a, b)| Op::Drain(a, b)),
+        1 => any::<u8>().prop_map(Op::Retain),
+        1 => any::<usize>().prop_map(Op::SplitOff),
+        1 => vec(any::<u8>(), 0..8).prop_map(Op::Append),
+        1 => (any::<usize>(), any::<usize>()).prop_map(|(a, b)| Op::ExtendFromWithin(a, b)),
+        1 => any::<usize>().prop_map(Op::Truncate),
+        1 => (0..32usize).prop_map(Op::Reserve),
+        1 => (0..32usize).prop_map(Op::ShrinkTo),
+        1 => Just(Op::ShrinkToFit),
+        1 => Just(Op::Clear),
+    ]
+}
+
+/// Element type of the model
+trait Elem: Clone + PartialEq + Debug {
+    type Ctx;
+
+    fn new(value: u8, ctx: &Self::Ctx) -> Self;
+
+    /// Alive elements in both the vector and the model, if counted by a static counter
+    fn alive() -> Option<isize> {
+        None
+    }
+}
+
+impl Elem for u8 {
+    type Ctx = ();
+
+    fn new(value: u8, _: &()) -> Self {
+        value
+    }
+}
+
+impl Elem for () {
+    type Ctx = ();
+
+    fn new(_: u8, _: &()) -> Self {}
+}
+
+/// Counts the alive elements, catch double drops and leaks
+#[derive(Debug)]
+struct Tracked {
+    value: u8,
+    alive: Rc<Cell<isize>>,
+}
+
+impl Clone for Tracked {
+    fn clone(&self) -> Self {
+        Self::new(self.value, &self.alive)
+    }
+}
+
+impl PartialEq for Tracked {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.alive.set(self.alive.get() - 1);
+    }
+}
+
+impl Elem for Tracked {
+    type Ctx = Rc<Cell<isize>>;
+
+    fn new(value: u8, alive: &Rc<Cell<isize>>) -> Self {
+        alive.set(alive.get() + 1);
+        Self { value, alive: alive.clone() }
+    }
+}
+
+thread_local! {
+    static ZST_ALIVE: Cell<isize> = const { Cell::new(0) };
+}
+
+/// Zero sized [`Tracked`], counts the alive elements by [`ZST_ALIVE`]
+#[derive(Debug, PartialEq)]
+struct TrackedZst;
+
+impl Clone for TrackedZst {
+    fn clone(&self) -> Self {
+        Self::new(0, &())
+    }
+}
+
+impl Drop for TrackedZst {
+    fn drop(&mut self) {
+        ZST_ALIVE.set(ZST_ALIVE.get() - 1);
+    }
+}
+
+impl Elem for TrackedZst {
+    type Ctx = ();
+
+    fn new(_: u8, _: &()) -> Self {
+        ZST_ALIVE.set(ZST_ALIVE.get() + 1);
+        Self
+    }
+
+    fn alive() -> Option<isize> {
+        Some(ZST_ALIVE.get())
+    }
+}
+
+fn apply<T: Elem>(
+    ops: &[Op],
+    ctx: &T::Ctx,
+    model_ctx: &T::Ctx,
+    alive: impl Fn() -> Option<(isize, isize)>,
+) {
+    let mut vec: RcVec<T> = RcVec::new();
+    let mut model: Vec<T> = Vec::new();
+
+    for op in ops {
+        let len = model.len();
+
+        match *op {
+            Op::Push(v) => {
+                vec.push(T::new(v, ctx));
+                model.push(T::new(v, model_ctx));
+            },
+            Op::Pop => {
+                assert_eq!(vec.pop(), model.pop());
+            },
+            Op::Insert(i, v) => {
+                let i = i % (len + 1);
+                vec.insert(i, T::new(v, ctx));
+                model.insert(i, T::new(v, model_ctx));
+            },
+            Op::Remove(i) if len != 0 => {
+                assert_eq!(vec.remove(i % len), model.remove(i % len));
+            },
+            Op::SwapRemove(i) if len != 0 => {
+                assert_eq!(vec.swap_remove(i % len), model.swap_remove(i % len));
+            },
+            Op::Remove(_) | Op::SwapRemove(_) => (),
+            Op::Drain(a, b) => {
+                let start = a % (len + 1);
+                let end = start + b % (len - start + 1);
+                assert!(vec.drain(start..end).eq(model.drain(start..end)));
+            },
+            Op::Retain(pattern) => {
+                let (mut i, mut j) = (0, 0);
+                vec.retain(|_| { i += 1; pattern >> (i % 8) & 1 == 1 });
+                model.retain(|_| { j += 1; pattern >> (j % 8) & 1 == 1 });
+            },
+            Op::SplitOff(at) => {
+                let at = at % (len + 1);
+                let other = vec.split_off(at);
+                assert_eq!(*other, model.split_off(at));
+            },
+            Op::Append(ref values) => {
+                let mut other: RcVec<T> = values.iter().map(|&v| T::new(v, ctx)).collect();
+                vec.append(&mut other);
+                assert!(other.is_empty());
+                model.extend(values.iter().map(|&v| T::new(v, model_ctx)));
+            },
+            Op::ExtendFromWithin(a, b) => {
+                let start = a % (len + 1);
+                let end = start + b % (len - start + 1);
+                vec.extend_from_within(start..end);
+                model.extend_from_within(start..end);
+            },
+            Op::Truncate(n) => {
+                let n = n % (len + 1);
+                vec.truncate(n);
+                model.truncate(n);
+            },
+            Op::Reserve(n) => {
+                vec.reserve(n);
+                assert!(vec.capacity() >= len + n);
+            },
+            Op::ShrinkTo(n) => {
+                vec.shrink_to(n);
+                assert!(vec.capacity() >= len);
+            },
+            Op::ShrinkToFit => {
+                vec.shrink_to_fit();
+                assert!(vec.capacity() >= len);
+            },
+            Op::Clear => {
+                vec.clear();
+                model.clear();
+            },
+        }
+
+        assert_eq!(vec.len(), model.len());
+        assert_eq!(*vec, model);
+
+        if let Some((alive, model_alive)) = alive() {
+            assert_eq!(alive, model_alive);
+            assert_eq!(alive, vec.len() as isize);
+        }
+        if let Some(alive) = T::alive() {
+            assert_eq!(alive, (vec.len() + model.len()) as isize);
+        }
+    }
+
+    drop(vec);
+    drop(model);
+    assert!(alive().is_none_or(|counts| counts == (0, 0)));
+    assert!(T::alive().is_none_or(|alive| alive == 0));
+}
+
+#[derive(Debug, Clone)]
+enum DequeOp {
+    PushBack(u8),
+    PushFront(u8),
+    PopBack,
+    PopFront,
+    Reserve(usize),
+    MakeContiguous,
+}
+
+fn deque_op() -> impl Strategy<Value = DequeOp> {
+    prop_oneof![
+        3 => any::<u8>().prop_map(DequeOp::PushBack),
+        3 => any::<u8>().prop_map(DequeOp::PushFront),
+        2 => Just(DequeOp::PopBack),
+        2 => Just(DequeOp::PopFront),
+        1 => (0..32usize).prop_map(DequeOp::Reserve),
+        1 => Just(DequeOp::MakeContiguous),
+    ]
+}
+
+fn apply_deque(ops: &[DequeOp]) {
+    let mut deque: RcVecDeque<u8> = RcVecDeque::new();
+    let mut model: VecDeque<u8> = VecDeque::new();
+
+    for op in ops {
+        match *op {
+            DequeOp::PushBack(v) => {
+                deque.push_back(v);
+                model.push_back(v);
+            },
+            DequeOp::PushFront(v) => {
+                deque.push_front(v);
+                model.push_front(v);
+            },
+            DequeOp::PopBack => assert_eq!(deque.pop_back(), model.pop_back()),
+            DequeOp::PopFront => assert_eq!(deque.pop_front(), model.pop_front()),
+            DequeOp::Reserve(n) => {
+                deque.reserve(n);
+                assert!(deque.capacity() >= deque.len() + n);
+            },
+            DequeOp::MakeContiguous => {
+                assert_eq!(deque.make_contiguous(), model.make_contiguous());
+            },
+        }
+
+        assert_eq!(deque.len(), model.len());
+        assert!(deque.iter().eq(&model));
+    }
+
+    let rc = deque.into_rc_slice();
+    assert!(rc.iter().eq(&model));
+}
+
+proptest! {
+    #[test]
+    fn model_u8(ops in vec(op(), 0..64)) {
+        apply::<u8>(&ops, &(), &(), || None);
+    }
+
+    #[test]
+    fn model_zst(ops in vec(op(), 0..64)) {
+        apply::<()>(&ops, &(), &(), || None);
+    }
+
+    #[test]
+    fn model_tracked_zst(ops in vec(op(), 0..64)) {
+        // reset after a failed case
+        ZST_ALIVE.set(0);
+
+        apply::<TrackedZst>(&ops, &(), &(), || None);
+    }
+
+    #[test]
+    fn model_tracked(ops in vec(op(), 0..64)) {
+        let alive = Rc::new(Cell::new(0));
+        let model_alive = Rc::new(Cell::new(0));
+
+        apply::<Tracked>(&ops, &alive, &model_alive, || Some((alive.get(), model_alive.get())));
+    }
+
+    #[test]
+    fn model_deque(ops in vec(deque_op(), 0..64)) {
+        apply_deque(&ops);
+    }
+}